@@ -0,0 +1,96 @@
+// Server-wide configuration, read once at startup from environment variables
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub guess_cooldown_ms: u64, // Minimum gap between two guesses from the same player
+    pub very_close_streak: u32, // Near-miss guesses in a row before the feedback escalates to "very close" (0 never escalates)
+    pub max_connections_per_ip: usize, // Concurrent WebSocket connections allowed from one IP
+    pub all_guessed_advance_ms: u64, // Pause after everyone has guessed before the round ends
+    pub min_round_secs: u64, // An all-guessed round still runs at least this long after it started
+    pub max_rooms_per_creator: usize, // Active rooms one username may have created at once
+    pub max_rooms: usize, // Rooms the server will host at once; readiness fails at this cap
+    pub debug_endpoints: bool, // Record per-room event logs and serve them under /debug
+    pub max_ws_message_bytes: usize, // Largest inbound WebSocket message; bigger ones close the connection
+    pub max_path_strokes: usize, // Most strokes in one DrawUpdate path; longer paths are rejected
+    pub max_hint_reveal: u32, // Ceiling on a room's hint_count, the letters auto-revealed per round
+    pub word_select_secs: u32, // Word-selection countdown broadcast to the room each second (0 disables)
+    pub disconnect_grace_secs: u64, // How long a dropped player keeps their seat and score before removal
+    pub transfer_host_on_disconnect: bool, // Hand the room on as soon as the host drops, not after the grace window
+    pub skip_selecting_drawer_on_disconnect: bool, // A drawer who drops while choosing a word loses the turn at once
+    pub pause_for_disconnected_drawer: bool, // A drawer who drops mid-round freezes the clock for the grace window instead of losing the turn
+    pub connect_deadline_secs: u64, // REST-joined players who haven't opened a WebSocket by then are removed
+    pub ws_ticket_secs: u64, // How long a one-shot /ws ticket from POST /rooms stays redeemable
+    pub post_game_timeout_secs: u64, // A finished room nobody votes to replay is closed after this long
+    pub round_intermission_secs: u32, // Pause on the round summary before the next drawer is announced and offered words
+    pub max_total_game_secs: u64, // Settings whose longest possible game runs past this are rejected (0 disables)
+    pub admin_token: Option<String>, // Secret for read-only admin observers on /ws; unset disables observing
+    pub debug_secret: Option<String>, // Required in x-debug-secret for full room dumps; unset disables them
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            guess_cooldown_ms: 500,
+            very_close_streak: 2,
+            max_connections_per_ip: 10,
+            all_guessed_advance_ms: 2000,
+            min_round_secs: 5,
+            max_rooms_per_creator: 5,
+            max_rooms: 1000,
+            debug_endpoints: false,
+            max_ws_message_bytes: 2 * 1024 * 1024,
+            max_path_strokes: 1000,
+            max_hint_reveal: 4,
+            word_select_secs: 15,
+            disconnect_grace_secs: 60,
+            transfer_host_on_disconnect: true,
+            skip_selecting_drawer_on_disconnect: true,
+            pause_for_disconnected_drawer: false,
+            connect_deadline_secs: 30,
+            ws_ticket_secs: 30,
+            post_game_timeout_secs: 120,
+            round_intermission_secs: 5,
+            max_total_game_secs: 3 * 60 * 60,
+            admin_token: None,
+            debug_secret: None,
+        }
+    }
+}
+
+impl ServerConfig {
+    // Build a config from the environment, falling back to defaults for missing/invalid values
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            guess_cooldown_ms: env_or("GUESS_COOLDOWN_MS", defaults.guess_cooldown_ms),
+            very_close_streak: env_or("VERY_CLOSE_STREAK", defaults.very_close_streak),
+            max_connections_per_ip: env_or("MAX_CONNECTIONS_PER_IP", defaults.max_connections_per_ip),
+            all_guessed_advance_ms: env_or("ALL_GUESSED_ADVANCE_MS", defaults.all_guessed_advance_ms),
+            min_round_secs: env_or("MIN_ROUND_SECS", defaults.min_round_secs),
+            max_rooms_per_creator: env_or("MAX_ROOMS_PER_CREATOR", defaults.max_rooms_per_creator),
+            max_rooms: env_or("MAX_ROOMS", defaults.max_rooms),
+            debug_endpoints: env_or("DEBUG_ENDPOINTS", defaults.debug_endpoints),
+            max_ws_message_bytes: env_or("MAX_WS_MESSAGE_BYTES", defaults.max_ws_message_bytes),
+            max_path_strokes: env_or("MAX_PATH_STROKES", defaults.max_path_strokes),
+            max_hint_reveal: env_or("MAX_HINT_REVEAL", defaults.max_hint_reveal),
+            word_select_secs: env_or("WORD_SELECT_SECS", defaults.word_select_secs),
+            disconnect_grace_secs: env_or("DISCONNECT_GRACE_SECS", defaults.disconnect_grace_secs),
+            transfer_host_on_disconnect: env_or("TRANSFER_HOST_ON_DISCONNECT", defaults.transfer_host_on_disconnect),
+            skip_selecting_drawer_on_disconnect: env_or("SKIP_SELECTING_DRAWER_ON_DISCONNECT", defaults.skip_selecting_drawer_on_disconnect),
+            pause_for_disconnected_drawer: env_or("PAUSE_FOR_DISCONNECTED_DRAWER", defaults.pause_for_disconnected_drawer),
+            connect_deadline_secs: env_or("CONNECT_DEADLINE_SECS", defaults.connect_deadline_secs),
+            ws_ticket_secs: env_or("WS_TICKET_SECS", defaults.ws_ticket_secs),
+            post_game_timeout_secs: env_or("POST_GAME_TIMEOUT_SECS", defaults.post_game_timeout_secs),
+            round_intermission_secs: env_or("ROUND_INTERMISSION_SECS", defaults.round_intermission_secs),
+            max_total_game_secs: env_or("MAX_TOTAL_GAME_SECS", defaults.max_total_game_secs),
+            admin_token: std::env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty()),
+            debug_secret: std::env::var("DEBUG_SECRET").ok().filter(|secret| !secret.is_empty()),
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
@@ -10,16 +10,20 @@ use axum::{
 use std::net::SocketAddr;
 use tower_http::cors::{CorsLayer, Any};
 use axum::extract::ws::Message;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::StreamExt;
 
+mod config;
 mod models;
 mod state;
 mod utils;
 mod websocket;
 mod scoring;
+mod words;
+#[cfg(test)]
+mod test_support;
 
 use models::*;
-use state::AppState;
+use state::{AppState, IpConnectionSlot, WsTicket, secrets_match};
 
 use uuid::Uuid;
 
@@ -34,49 +38,134 @@ async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+// Liveness: the process is up and serving requests
+async fn health_live() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        message: "alive".to_string(),
+    })
+}
+
+// Readiness: the server can accept new rooms
+async fn health_ready(
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> (StatusCode, Json<HealthResponse>) {
+    if state.at_room_capacity() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(HealthResponse {
+                status: "unavailable".to_string(),
+                message: "Room capacity reached".to_string(),
+            })
+        );
+    }
+    (
+        StatusCode::OK,
+        Json(HealthResponse {
+            status: "ok".to_string(),
+            message: "ready".to_string(),
+        })
+    )
+}
+
+/// Report which custom words a room would accept, without creating one
+async fn validate_words(Json(payload): Json<ValidateWordsRequest>) -> Json<ValidateWordsResponse> {
+    let min_word_length = payload.min_word_length.unwrap_or(words::DEFAULT_MIN_WORD_LENGTH);
+    let (accepted, rejected) = words::review_custom_words(&payload.words, min_word_length);
+    Json(ValidateWordsResponse {
+        usable_count: accepted.len(),
+        enough: accepted.len() >= words::WORD_CHOICE_COUNT,
+        accepted,
+        rejected,
+    })
+}
+
 async fn create_room(
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(payload): Json<CreateRoomRequest>
 ) -> (StatusCode, Json<CreateRoomResponse>) {
-    let room_code = state.generate_room_code();
-    
-    let player_id = Uuid::new_v4();
-    let player = Player {
-        id: player_id,
-        username: payload.username.clone(),
-        score: 0,
-        state: PlayerState::Spectator,
-        is_connected: true,
-        is_drawing: false,
-        joined_at: chrono::Utc::now(),
-        artist_streak: 0,
+    let (status, response) = create_room_for(&state, payload);
+    (status, Json(response))
+}
+
+/// POST /rooms: create the room and hand back a one-shot ticket so the client's
+/// /ws upgrade binds straight to the new host instead of matching on username
+async fn create_room_with_ticket(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(payload): Json<CreateRoomRequest>
+) -> (StatusCode, Json<CreateRoomWithTicketResponse>) {
+    let (status, created) = create_room_for(&state, payload);
+    let ws_ticket = match (&created.room, &created.player) {
+        (Some(room), Some(player)) => Some(state.issue_ws_ticket(player.id, &room.code)),
+        _ => None,
     };
+    (status, Json(CreateRoomWithTicketResponse { created, ws_ticket }))
+}
+
+fn create_room_for(state: &AppState, payload: CreateRoomRequest) -> (StatusCode, CreateRoomResponse) {
+    let min_word_length = payload.min_word_length.unwrap_or(words::DEFAULT_MIN_WORD_LENGTH);
+    let custom_words = match payload.custom_words.as_deref() {
+        Some(list) => match words::validate_custom_words(list, min_word_length) {
+            Ok(cleaned) => cleaned,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    CreateRoomResponse {
+                        success: false,
+                        message: e,
+                        room: None,
+                        player: None,
+                        auth_token: None,
+                    }
+                );
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let player = Player { is_connected: false, ..Player::new(&payload.username) };
+    let player_id = player.id;
     
-    let _room = state.create_room(room_code.clone(), payload.round_duration, 8, player_id);
-    
-    if let Err(_e) = state.add_player_to_room(&room_code, player.clone()) {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(CreateRoomResponse {
-                success: false,
-                message: "Failed to add player to room".to_string(),
-                room: None,
-                player: None,
+    // The host is added through the same atomic gate as every other joiner
+    // Practice rooms are solo
+    let max_players = if payload.practice { 1 } else { websocket::rooms::MAX_PLAYERS_PER_ROOM };
+    let room = match state.create_room_with_host(payload.round_duration, max_players, player.clone()) {
+        Ok(room) => state
+            .with_room_mut(&room.code, |room| {
+                room.custom_words = custom_words;
+                room.min_word_length = min_word_length;
+                room.practice = payload.practice;
+                room.persistent = payload.persistent;
+                room.clone()
             })
-        );
-    }
+            .unwrap_or(room),
+        Err(e) => {
+            println!("Failed to create room: {}", e);
+            return (
+                StatusCode::BAD_REQUEST,
+                CreateRoomResponse {
+                    success: false,
+                    message: e,
+                    room: None,
+                    player: None,
+                    auth_token: None,
+                }
+            );
+        }
+    };
     
-    // Get the created room
-    let room = state.get_room(&room_code).unwrap();
+    let auth_token = state.issue_auth_token(player_id);
+    websocket::rooms::start_connect_deadline(state, &room.code, player_id);
     
     (
         StatusCode::CREATED,
-        Json(CreateRoomResponse {
+        CreateRoomResponse {
             success: true,
             message: "Room created successfully".to_string(),
-            room: Some(room.clone()),
+            room: Some(room),
             player: Some(player),
-        })
+            auth_token: Some(auth_token),
+        }
     )
 }
 
@@ -92,42 +181,46 @@ async fn join_room(
                 message: "Room not found".to_string(),
                 room: None,
                 player: None,
+                auth_token: None,
+                spectator: false,
             })
         );
     }
     
-    let player_id = Uuid::new_v4();
-    let player = Player {
-        id: player_id,
-        username: payload.username.clone(),
-        score: 0,
-        state: PlayerState::Spectator,
-        is_connected: true,
-        is_drawing: false,
-        joined_at: chrono::Utc::now(),
-        artist_streak: 0,
-    };
+    // Not connected until the WebSocket join attaches a socket
+    let player = Player { is_connected: false, ..Player::new(&payload.username) };
+    let player_id = player.id;
     
-    match state.add_player_to_room(&payload.room_code, player.clone()) {
-        Ok(_) => {
-            let room = state.get_room(&payload.room_code).unwrap();
+    match state.add_player_to_room(&payload.room_code, player) {
+        Ok(player) => {
+            websocket::rooms::start_connect_deadline(&state, &payload.room_code, player_id);
+            // Mid-game joiners were appended to the rotation
+            websocket::rooms::broadcast_draw_order(&state, &payload.room_code);
+            // Mask the word and winners-only chat exactly as the WebSocket views do
+            let room = AppState::room_view_for(&state.get_room(&payload.room_code).unwrap(), &player_id);
+            let auth_token = state.issue_auth_token(player_id);
+            let spectator = player.state == PlayerState::Spectator;
             (
                 StatusCode::OK,
                 Json(JoinRoomResponse {
                     success: true,
-                    message: "Joined room successfully".to_string(),
-                    room: Some(room.clone()),
+                    message: if spectator { "Room is full, joined as a spectator" } else { "Joined room successfully" }.to_string(),
+                    room: Some(room),
                     player: Some(player),
+                    auth_token: Some(auth_token),
+                    spectator,
                 })
             )
         },
-        Err(_e) => (
+        Err(e) => (
             StatusCode::BAD_REQUEST,
             Json(JoinRoomResponse {
                 success: false,
-                message: "Failed to join room".to_string(),
+                message: e,
                 room: None,
                 player: None,
+                auth_token: None,
+                spectator: false,
             })
         ),
     }
@@ -161,40 +254,53 @@ async fn leave_room(
         ),
     };
     
-    if let Some(room) = state.get_room(&room_code) {
-        if !room.players.contains_key(&player_id) {
-            return (
-                StatusCode::FORBIDDEN, 
-                Json(serde_json::json!({
-                    "success": false,
-                    "error": "Player is not in this room"
-                }))
-            );
-        }
+    // The token proves the caller is the player they claim to be
+    if !state.validate_auth_token(&player_id, payload.auth_token.trim()) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "Invalid auth token for this player"
+            }))
+        );
+    }
+    
+    if let Some(room) = state.get_room(&room_code)
+        && !room.players.contains_key(&player_id) {
+        return (
+            StatusCode::FORBIDDEN, 
+            Json(serde_json::json!({
+                "success": false,
+                "error": "Player is not in this room"
+            }))
+        );
     }
     
+    // Capture host status before removal, matching the WS leave flow
+    let was_host = state
+        .get_room(&room_code)
+        .is_some_and(|room| room.host_id == player_id);
+    let was_drawing = state
+        .get_room(&room_code)
+        .is_some_and(|room| websocket::rooms::holds_turn(&room, player_id));
+    
     match state.remove_player_from_room(&room_code, &player_id) {
         Ok((player, room_will_be_empty)) => {
-            // Check if this was the host and transfer ownership if needed
+            if was_host {
+                websocket::rooms::reassign_host_after_leave(&state, &room_code);
+            }
+            
+            // Let everyone still in the room know
             if !room_will_be_empty {
-                if let Some(room) = state.get_room(&room_code) {
-                    if room.host_id == player_id {
-                        // This was the host, transfer ownership
-                        if let Ok(new_host_id) = state.transfer_host_ownership(&room_code) {
-                            if let Some(new_host) = room.players.get(&new_host_id) {
-                                println!("Host ownership transferred to {}", new_host.username);
-                                
-                                // Broadcast host change to remaining players
-                                let host_change_msg = ServerMessage::HostChanged {
-                                    new_host: new_host.clone(),
-                                };
-                                if let Ok(json) = serde_json::to_string(&host_change_msg) {
-                                    state.broadcast_to_room(&room_code, Message::Text(json));
-                                }
-                            }
-                        }
-                    }
+                let left_msg = ServerMessage::PlayerLeft {
+                    room_code: room_code.clone(),
+                    player: player.clone(),
+                };
+                if let Ok(json) = serde_json::to_string(&left_msg) {
+                    state.broadcast_to_room(&room_code, Message::Text(json));
                 }
+                websocket::rooms::broadcast_draw_order(&state, &room_code);
+                websocket::rooms::pass_turn_from_departed_drawer(&state, &room_code, was_drawing, DrawerChangeReason::Skip);
             }
             
             (
@@ -215,13 +321,299 @@ async fn leave_room(
     }
 }
 
+async fn get_replay(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+    axum::extract::Query(auth): axum::extract::Query<PlayerAuthQuery>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let room_code = room_code.trim().to_uppercase();
+    let Some(room) = state.get_room(&room_code) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "Room not found"
+            }))
+        );
+    };
+    
+    // While a word is in play the drawing can give it away, so only players who
+    // already know the word (the artist and correct guessers) may replay it
+    if room.word.is_some() {
+        let caller = auth.player_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id.trim()).ok())
+            .filter(|id| state.validate_auth_token(id, auth.auth_token.as_deref().unwrap_or("").trim()));
+        let allowed = caller
+            .map(|id| room.current_drawer == Some(id) || room.winners.contains(&id))
+            .unwrap_or(false);
+        if !allowed {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": "The current round's drawing is only available to players who guessed it"
+                }))
+            );
+        }
+    }
+    
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "success": true,
+            "room_code": room_code,
+            "paths": room.drawing_paths,
+        }))
+    )
+}
+
+async fn get_gallery(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let room_code = room_code.trim().to_uppercase();
+    match state.get_room(&room_code) {
+        // Finished rounds have already revealed their word, so the gallery is public
+        Some(room) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "success": true,
+                "room_code": room_code,
+                "gallery": room.gallery,
+            }))
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "Room not found"
+            }))
+        ),
+    }
+}
+
+async fn get_room_player(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path((room_code, player_id)): axum::extract::Path<(String, String)>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let room_code = room_code.trim().to_uppercase();
+    let player = Uuid::parse_str(player_id.trim())
+        .ok()
+        .and_then(|id| state.get_room(&room_code)?.players.get(&id).cloned());
+    match player {
+        // Only public fields; nothing about the round the player may know
+        Some(player) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "success": true,
+                "player": {
+                    "id": player.id,
+                    "username": player.username,
+                    "score": player.score,
+                    "state": player.state,
+                    "artist_streak": player.artist_streak,
+                    "guess_streak": player.guess_streak,
+                    "is_connected": player.is_connected,
+                },
+            }))
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "Player not found in room"
+            }))
+        ),
+    }
+}
+
+/// Who is in the room and whether they're connected; players in their disconnect grace
+/// window are still listed, with is_connected false
+async fn get_room_presence(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let room_code = room_code.trim().to_uppercase();
+    let Some(room) = state.get_room(&room_code) else {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "success": false, "error": "Room not found" })));
+    };
+    let mut players: Vec<&Player> = room.players.values().collect();
+    players.sort_by_key(|p| p.joined_at);
+    let players: Vec<_> = players
+        .into_iter()
+        .map(|p| serde_json::json!({
+            "id": p.id,
+            "username": p.username,
+            "is_connected": p.is_connected,
+            "state": p.state,
+        }))
+        .collect();
+    (StatusCode::OK, Json(serde_json::json!({ "success": true, "room_code": room_code, "players": players })))
+}
+
+async fn get_room_scoring(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let room_code = room_code.trim().to_uppercase();
+    match state.get_room(&room_code) {
+        // Rooms that never customized scoring carry the defaults
+        Some(room) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "success": true,
+                "room_code": room_code,
+                "scoring": room.scoring,
+            }))
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "success": false, "error": "Room not found" }))
+        ),
+    }
+}
+
+async fn get_room_events(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    // Diagnostic only; pretend the route doesn't exist unless explicitly enabled
+    if !state.config.debug_endpoints {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "success": false, "error": "Not found" })));
+    }
+    let room_code = room_code.trim().to_uppercase();
+    if state.get_room(&room_code).is_none() {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "success": false, "error": "Room not found" })));
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "success": true,
+            "room_code": room_code,
+            "events": state.room_events(&room_code),
+        }))
+    )
+}
+
+/// Dump the complete unfiltered room (word, winners, chat, drawing) for reproducing bug reports.
+/// Without the configured secret in x-debug-secret the route looks like it doesn't exist.
+async fn get_room_full(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Path(room_code): axum::extract::Path<String>,
+    headers: axum::http::HeaderMap,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let given = headers.get("x-debug-secret").and_then(|value| value.to_str().ok());
+    let authorized = matches!((&state.config.debug_secret, given), (Some(expected), Some(given)) if secrets_match(expected, given));
+    if !authorized {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "success": false, "error": "Not found" })));
+    }
+    let room_code = room_code.trim().to_uppercase();
+    let Some(room) = state.get_room(&room_code) else {
+        return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "success": false, "error": "Room not found" })));
+    };
+    (StatusCode::OK, Json(serde_json::json!({ "success": true, "room": room })))
+}
+
 async fn websocket_handler(
     ws: WebSocketUpgrade,
+    axum::extract::Query(query): axum::extract::Query<WsConnectQuery>,
+    axum::extract::ConnectInfo(peer_addr): axum::extract::ConnectInfo<SocketAddr>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
+    let protocol_version = match websocket::protocol::negotiate(query.protocol_version) {
+        Ok(version) => version,
+        Err(e) => {
+            println!("Refusing WebSocket from {}: {}", peer_addr, e);
+            return (StatusCode::BAD_REQUEST, e).into_response();
+        }
+    };
+    // Refuse the upgrade if this IP already has too many open connections
+    let Some(ip_slot) = state.try_acquire_ip_slot(peer_addr.ip()) else {
+        println!("Refusing WebSocket from {}: per-IP connection limit reached", peer_addr);
+        return (StatusCode::TOO_MANY_REQUESTS, "Too many connections from this address").into_response();
+    };
+    // Admin observers must present the configured token; with none configured, observing is off
+    let observe_room = match query.observe {
+        Some(room_code) => {
+            let authorized = matches!(
+                (&state.config.admin_token, &query.admin_token),
+                (Some(expected), Some(given)) if secrets_match(expected, given)
+            );
+            if !authorized {
+                println!("Refusing admin observer from {}: bad or missing token", peer_addr);
+                return (StatusCode::FORBIDDEN, "Observing requires a valid admin token").into_response();
+            }
+            let room_code = room_code.trim().to_uppercase();
+            if state.get_room(&room_code).is_none() {
+                return (StatusCode::NOT_FOUND, "Room not found").into_response();
+            }
+            Some(room_code)
+        }
+        None => None,
+    };
+    // A ticket is good for exactly one upgrade, so it is consumed here even if the socket never opens
+    let ticket = match query.ws_ticket {
+        Some(ticket) => match state.redeem_ws_ticket(ticket.trim()) {
+            Some(ticket) => Some(ticket),
+            None => {
+                println!("Refusing WebSocket from {}: unknown or expired ticket", peer_addr);
+                return (StatusCode::UNAUTHORIZED, "Invalid or expired ws_ticket").into_response();
+            }
+        },
+        None => None,
+    };
+    // Oversized frames fail the read, which ends the connection before anything is parsed
+    let max_message_bytes = state.config.max_ws_message_bytes;
+    ws.max_message_size(max_message_bytes)
+        .max_frame_size(max_message_bytes)
+        .on_upgrade(move |socket| async move {
+            match observe_room {
+                Some(room_code) => handle_observer_socket(socket, state, peer_addr, ip_slot, protocol_version, room_code).await,
+                None => handle_socket(socket, state, peer_addr, ip_slot, protocol_version, ticket).await,
+            }
+        })
 }
 
+/// Serve a read-only admin observer: it gets the unfiltered room (word included) and
+/// every chat message, and anything it sends is refused
+async fn handle_observer_socket(socket: WebSocket, state: AppState, peer_addr: SocketAddr, _ip_slot: IpConnectionSlot, protocol_version: u32, room_code: String) {
+    let (sender, mut receiver) = socket.split();
+    println!("Admin observer from {} watching room {}", peer_addr, room_code);
+
+    let (tx, rx) = state::connection_channel();
+    tokio::spawn(websocket::envelope::forward_messages(rx, sender, protocol_version));
+    let observer_id = state.add_observer(room_code.clone(), tx.clone());
+
+    if let Some(room) = state.get_room(&room_code) {
+        let state_msg = ServerMessage::GameStateUpdate { room };
+        if let Ok(json) = serde_json::to_string(&state_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+    }
+
+    while let Some(msg) = tokio::select! {
+        msg = receiver.next() => msg,
+        _ = tx.evicted() => None,
+    } {
+        match msg {
+            Ok(Message::Text(_)) => {
+                let error_msg = ServerMessage::Error {
+                    message: "Observers are read-only".to_string(),
+                    severity: ErrorSeverity::Warning,
+                };
+                if let Ok(json) = serde_json::to_string(&error_msg) {
+                    let _ = tx.send(Message::Text(json));
+                }
+            },
+            Ok(Message::Close(_)) | Err(_) => break,
+            _ => {}
+        }
+    }
+
+    state.remove_connection(&observer_id);
+    println!("Admin observer from {} left room {}", peer_addr, room_code);
+}
 
 
 
@@ -230,28 +622,37 @@ async fn websocket_handler(
 
 
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
+
+async fn handle_socket(socket: WebSocket, state: AppState, peer_addr: SocketAddr, _ip_slot: IpConnectionSlot, protocol_version: u32, ticket: Option<WsTicket>) {
     let (sender, mut receiver) = socket.split();
-    println!("New WebSocket connection established");
+    println!("New WebSocket connection established from {} (protocol v{})", peer_addr, protocol_version);
     
-    // Create a channel for sending messages back to this connection
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Message>();
+    // Create a bounded queue for sending messages back to this connection
+    let (tx, rx) = state::connection_channel();
     
-    // Spawn a task to forward messages from the channel to the WebSocket
-    let mut sender_task = sender;
-    tokio::spawn(async move {
-        while let Some(message) = rx.recv().await {
-            if let Err(e) = sender_task.send(message).await {
-                println!("Failed to send message: {}", e);
-                break;
-            }
-        }
-    });
+    // Spawn a task to forward messages from the channel to the WebSocket,
+    // wrapping each in a sequenced envelope for clients that speak v2
+    tokio::spawn(websocket::envelope::forward_messages(rx, sender, protocol_version));
     
     let mut current_player_id: Option<Uuid> = None;
     let mut current_room_code: Option<String> = None;
+
+    if let Some(ticket) = ticket {
+        websocket::rooms::handle_join_with_ticket(&state, &ticket.room_code, ticket.player_id, &tx, &mut current_player_id, &mut current_room_code).await;
+    }
     
-    while let Some(msg) = receiver.next().await {
+    // Stop serving the socket if it falls too far behind on outbound messages
+    while let Some(msg) = tokio::select! {
+        msg = receiver.next() => msg,
+        _ = tx.evicted() => {
+            println!("Dropping slow consumer {} (send queue full)", peer_addr);
+            None
+        }
+        _ = tx.closed() => {
+            println!("Closing connection {} after a fatal error", peer_addr);
+            None
+        }
+    } {
         match msg {
             Ok(Message::Text(text)) => {
                 println!("Received message: {}", text);
@@ -260,19 +661,22 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                     Ok(client_msg) => {
                         println!("Successfully parsed message: {:?}", client_msg);
                         match client_msg {
-                            ClientMessage::JoinRoom { room_code, username } => {
+                            ClientMessage::JoinRoom { room_code, username, auth_token } => {
                                 println!("Calling handle_join_room for {} in room {}", username, room_code);
-                                websocket::rooms::handle_join_room(&state, &room_code, &username, &tx, &mut current_player_id, &mut current_room_code).await;
+                                websocket::rooms::handle_join_room(&state, &room_code, &username, &auth_token, &tx, &mut current_player_id, &mut current_room_code).await;
+                            },
+                            ClientMessage::Reconnect { room_code, player_id, reconnect_token } => {
+                                websocket::rooms::handle_reconnect(&state, &room_code, &player_id, &reconnect_token, &tx, &mut current_player_id, &mut current_room_code).await;
                             },
                             ClientMessage::LeaveRoom { room_code, player_id } => {
                                 println!("Calling handle_leave_room for player {} in room {}", player_id, room_code);
                                 websocket::rooms::handle_leave_room(&state, &room_code, &player_id, &tx, &mut current_player_id, &mut current_room_code).await;
                             },
                             ClientMessage::DrawUpdate { room_code, path } => {
-                                websocket::drawing::handle_draw_update(&state, &room_code, &path, &tx).await;
+                                websocket::drawing::handle_draw_update(&state, &room_code, current_player_id, &path, &tx).await;
                             },
                             ClientMessage::DrawStroke { room_code, stroke } => {
-                                websocket::drawing::handle_draw_stroke(&state, &room_code, &stroke, &tx).await;
+                                websocket::drawing::handle_draw_stroke(&state, &room_code, current_player_id, &stroke, &tx).await;
                             },
                             ClientMessage::Chat { room_code, message } => {
                                 if let Some(player_id) = current_player_id {
@@ -289,23 +693,102 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                             ClientMessage::Guess { room_code, guess } => {
                                 websocket::chat::handle_guess(&state, &room_code, &guess, &tx).await;
                             },
-                            ClientMessage::StartGame { room_code } => {
-                                websocket::rooms::handle_start_game(&state, &room_code, &tx).await;
+                            ClientMessage::StartGame { room_code, force } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_start_game(&state, &room_code, player_id, force, &tx).await;
+                                }
+                            },
+                            ClientMessage::TransferHost { room_code, new_host_id } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_transfer_host(&state, &room_code, player_id, new_host_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::MutePlayer { room_code, target_player_id, muted } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_mute_player(&state, &room_code, player_id, target_player_id, muted, &tx).await;
+                                }
                             },
-                            ClientMessage::EndRound { room_code } => {
-                                websocket::rooms::handle_end_round(&state, &room_code, &tx).await;
+                            ClientMessage::KickPlayer { room_code, target_player_id } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_kick_player(&state, &room_code, player_id, target_player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::SetReady { room_code, ready } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_set_ready(&state, &room_code, player_id, ready, &tx).await;
+                                }
+                            },
+                            ClientMessage::EndRound { .. } => {
+                                // Only the room this socket joined; the code in the message isn't trusted
+                                if let (Some(player_id), Some(room_code)) = (current_player_id, current_room_code.clone()) {
+                                    websocket::rooms::handle_end_round(&state, &room_code, player_id, &tx).await;
+                                }
                             },
                             ClientMessage::WordSelected { room_code, word } => {
-                                websocket::rooms::handle_word_selected(&state, &room_code, &word, &tx).await;
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_word_selected(&state, &room_code, player_id, &word, &tx).await;
+                                }
                             },
-                            ClientMessage::UpdateSettings { room_code, max_rounds } => {
-                                websocket::rooms::handle_update_settings(&state, &room_code, max_rounds, &tx).await;
+                            ClientMessage::Undo { room_code } => {
+                                websocket::drawing::handle_undo(&state, &room_code, current_player_id, &tx).await;
                             },
-                            ClientMessage::WinnersChat { room_code, message } => {
+                            ClientMessage::ClearCanvas { room_code } => {
+                                websocket::drawing::handle_clear_canvas(&state, &room_code, current_player_id, &tx).await;
+                            },
+                            ClientMessage::NextWord { room_code } => {
                                 if let Some(player_id) = current_player_id {
-                                    if let Some(player) = state.get_player(&player_id) {
-                                        websocket::chat::handle_winners_chat(&state, &room_code, &message, player_id, &player.username).await;
-                                    }
+                                    websocket::rooms::handle_next_word(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::VoteSkip { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_vote_skip(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::GiveUp { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_give_up(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::RerollWords { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_reroll_words(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::AdjustScore { room_code, player_id: target_player_id, delta } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_adjust_score(&state, &room_code, player_id, target_player_id, delta, &tx).await;
+                                }
+                            },
+                            ClientMessage::BuyHint { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_buy_hint(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::RequestChatHistory { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::chat::handle_request_chat_history(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::PlayAgain { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_play_again(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::RequestScoreboard { room_code } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_request_scoreboard(&state, &room_code, player_id, &tx).await;
+                                }
+                            },
+                            ClientMessage::UpdateSettings { room_code, settings } => {
+                                if let Some(player_id) = current_player_id {
+                                    websocket::rooms::handle_update_settings(&state, &room_code, player_id, &settings, &tx).await;
+                                }
+                            },
+                            ClientMessage::WinnersChat { room_code, message } => {
+                                if let Some(player_id) = current_player_id
+                                    && let Some(player) = state.get_player(&player_id) {
+                                    websocket::chat::handle_winners_chat(&state, &room_code, &message, player_id, &player.username, &tx).await;
                                 }
                             }
                         }
@@ -314,6 +797,7 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                         println!("Failed to parse message: {}", e);
                         let error_msg = ServerMessage::Error {
                             message: "Invalid message format".to_string(),
+                            severity: ErrorSeverity::Warning,
                         };
                         if let Ok(json) = serde_json::to_string(&error_msg) {
                             let _ = tx.send(Message::Text(json));
@@ -333,28 +817,10 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         }
     }
     
-    // Clean up connection when socket closes
-    if let Some(player_id) = current_player_id {
-        state.remove_connection(&player_id);
-        if let Some(room_code) = &current_room_code {
-            // Notify other players that this player disconnected
-            let disconnect_msg =                 ServerMessage::PlayerLeft {
-                    room_code: room_code.clone(),
-                    player: Player {
-                        id: player_id,
-                        username: "Unknown".to_string(),
-                        score: 0,
-                        state: PlayerState::Disconnected,
-                        is_connected: false,
-                        is_drawing: false,
-                        joined_at: chrono::Utc::now(),
-                        artist_streak: 0,
-                    },
-                };
-            if let Ok(json) = serde_json::to_string(&disconnect_msg) {
-                state.broadcast_to_room(room_code, Message::Text(json));
-            }
-        }
+    // Clean up connection when socket closes, unless a newer socket has taken the player over
+    if let Some(player_id) = current_player_id
+        && state.owns_connection(&player_id, &tx) {
+        websocket::rooms::handle_disconnect(&state, player_id, current_room_code.as_deref()).await;
     }
     
     println!("WebSocket connection ended");
@@ -371,9 +837,20 @@ async fn main() {
 
     let app = Router::new()
         .route("/health", get(health_check))
+        .route("/health/live", get(health_live))
+        .route("/health/ready", get(health_ready))
         .route("/createRoom", post(create_room))
+        .route("/rooms", post(create_room_with_ticket))
+        .route("/words/validate", post(validate_words))
         .route("/joinRoom", post(join_room))
         .route("/leaveRoom", post(leave_room))
+        .route("/replay/:code", get(get_replay))
+        .route("/gallery/:code", get(get_gallery))
+        .route("/rooms/:code/players/:player_id", get(get_room_player))
+        .route("/rooms/:code/scoring", get(get_room_scoring))
+        .route("/rooms/:code/presence", get(get_room_presence))
+        .route("/debug/rooms/:code/events", get(get_room_events))
+        .route("/debug/rooms/:code/full", get(get_room_full))
         .route("/ws", get(websocket_handler))
         .layer(cors)
         .with_state(state);
@@ -382,12 +859,406 @@ async fn main() {
     println!("Skribbl Clone Backend starting on {}", addr);
     println!("Health check: http://localhost:3000/health");
     println!("Create room: POST http://localhost:3000/createRoom");
+    println!("Validate words: POST http://localhost:3000/words/validate");
     println!("Join room: POST http://localhost:3000/joinRoom");
     println!("Leave room: POST http://localhost:3000/leaveRoom");
+    println!("Replay: GET http://localhost:3000/replay/:code");
+    println!("Gallery: GET http://localhost:3000/gallery/:code");
+    println!("Scoring rules: GET http://localhost:3000/rooms/:code/scoring");
     println!("WebSocket: ws://localhost:3000/ws");
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     println!("Server listening on {}", addr);
     
-    serve(listener, app).await.unwrap();
+    serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await.unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use axum::extract::State;
+
+    async fn create(state: &AppState, username: &str) -> CreateRoomResponse {
+        let (_, Json(response)) = create_room(
+            State(state.clone()),
+            Json(CreateRoomRequest {
+                username: username.to_string(),
+                round_duration: 60,
+                custom_words: None,
+                min_word_length: None,
+                practice: false,
+                persistent: false,
+            }),
+        ).await;
+        response
+    }
+
+    async fn join(state: &AppState, room_code: &str, username: &str) -> JoinRoomResponse {
+        let (_, Json(response)) = join_room(
+            State(state.clone()),
+            Json(JoinRoomRequest { room_code: room_code.to_string(), username: username.to_string() }),
+        ).await;
+        response
+    }
+
+    async fn leave(state: &AppState, room_code: &str, player_id: Uuid, auth_token: &str) -> StatusCode {
+        let (status, _) = leave_room(
+            State(state.clone()),
+            Json(LeaveRoomRequest {
+                room_code: room_code.to_string(),
+                player_id: player_id.to_string(),
+                auth_token: auth_token.to_string(),
+            }),
+        ).await;
+        status
+    }
+
+    #[tokio::test]
+    async fn test_leave_with_another_players_id_is_forbidden() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        let guest = join(&state, &room_code, "guest").await;
+        let guest_id = guest.player.unwrap().id;
+
+        // The host's own token can't remove the guest
+        let status = leave(&state, &room_code, guest_id, &host.auth_token.unwrap()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(state.get_room(&room_code).unwrap().players.contains_key(&guest_id));
+
+        let status = leave(&state, &room_code, guest_id, &guest.auth_token.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!state.get_room(&room_code).unwrap().players.contains_key(&guest_id));
+    }
+
+    #[tokio::test]
+    async fn test_join_mid_round_hides_the_word() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        let host_id = host.player.unwrap().id;
+        test_support::start_round(&state, &room_code, host_id, "apple");
+
+        let room = join(&state, &room_code, "latecomer").await.room.unwrap();
+        assert_eq!(room.word, None);
+        assert_eq!(room.current_drawer, Some(host_id));
+    }
+
+    #[tokio::test]
+    async fn test_validate_words_reports_each_rejection() {
+        let words: Vec<String> = ["apple", "ox", "Apple", "shit", " castle ", "rocket"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let Json(response) = validate_words(Json(ValidateWordsRequest { words, min_word_length: None })).await;
+
+        assert_eq!(response.accepted, vec!["apple", "castle", "rocket"]);
+        let rejected: Vec<(&str, &str)> = response
+            .rejected
+            .iter()
+            .map(|r| (r.word.as_str(), r.reason.as_str()))
+            .collect();
+        assert_eq!(rejected, vec![
+            ("ox", "shorter than 3 characters"),
+            ("Apple", "duplicate"),
+            ("shit", "not allowed"),
+        ]);
+        assert_eq!(response.usable_count, 3);
+        assert!(response.enough);
+    }
+
+    #[tokio::test]
+    async fn test_player_who_never_connects_is_removed_after_deadline() {
+        let state = AppState::with_config(ServerConfig { connect_deadline_secs: 0, ..ServerConfig::default() });
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        let host_id = host.player.unwrap().id;
+        let _host_rx = test_support::connect(&state, &room_code, host_id);
+        let ghost_id = join(&state, &room_code, "ghost").await.player.unwrap().id;
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(!room.players.contains_key(&ghost_id));
+        assert!(room.players.contains_key(&host_id));
+    }
+
+    fn sample_path(player_id: Uuid) -> DrawPath {
+        DrawPath {
+            id: Uuid::new_v4(),
+            player_id,
+            color: Color::Black,
+            color_hex: "#000000".to_string(),
+            brush_size: BrushSize::Medium,
+            strokes: Vec::new(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_active_round_replay_denied_to_non_winner() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        let guest = join(&state, &room_code, "guest").await;
+        let host_id = host.player.unwrap().id;
+        let guest_id = guest.player.unwrap().id;
+        crate::test_support::start_round(&state, &room_code, host_id, "apple");
+        state.with_room_mut(&room_code, |room| room.drawing_paths.push(sample_path(host_id)));
+
+        let query = |id: Uuid, token: String| axum::extract::Query(PlayerAuthQuery {
+            player_id: Some(id.to_string()),
+            auth_token: Some(token),
+        });
+        let (status, _) = get_replay(State(state.clone()), axum::extract::Path(room_code.clone()), query(guest_id, guest.auth_token.clone().unwrap())).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // The artist already knows the word
+        let (status, Json(body)) = get_replay(State(state.clone()), axum::extract::Path(room_code.clone()), query(host_id, host.auth_token.unwrap())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["paths"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_finished_round_gallery_is_public() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        join(&state, &room_code, "guest").await;
+        let host_id = host.player.unwrap().id;
+        crate::test_support::start_round(&state, &room_code, host_id, "apple");
+        state.with_room_mut(&room_code, |room| room.drawing_paths.push(sample_path(host_id)));
+        state.end_round(&room_code).unwrap();
+
+        let (status, Json(body)) = get_gallery(State(state.clone()), axum::extract::Path(room_code.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        let gallery = body["gallery"].as_array().unwrap();
+        assert_eq!(gallery.len(), 1);
+        assert_eq!(gallery[0]["word"], "apple");
+        assert_eq!(gallery[0]["paths"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_simultaneous_same_username_joins_admit_exactly_one() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+
+        let (first, second) = tokio::join!(
+            tokio::spawn({
+                let (state, room_code) = (state.clone(), room_code.clone());
+                async move { join(&state, &room_code, "twin").await.success }
+            }),
+            tokio::spawn({
+                let (state, room_code) = (state.clone(), room_code.clone());
+                async move { join(&state, &room_code, "twin").await.success }
+            }),
+        );
+        assert!(first.unwrap() ^ second.unwrap());
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.players.values().filter(|p| p.username == "twin").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_room_adds_host_through_join_gate() {
+        let state = AppState::new();
+        let response = create(&state, "host").await;
+        let room = response.room.unwrap();
+        assert_eq!(room.host_id, response.player.unwrap().id);
+        assert_eq!(room.players.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ws_ticket_binds_socket_to_created_host_once() {
+        let state = AppState::new();
+        let (status, Json(response)) = create_room_with_ticket(
+            State(state.clone()),
+            Json(CreateRoomRequest {
+                username: "host".to_string(),
+                round_duration: 60,
+                custom_words: None,
+                min_word_length: None,
+                practice: false,
+                persistent: false,
+            }),
+        ).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let host_id = response.created.player.unwrap().id;
+        let ws_ticket = response.ws_ticket.unwrap();
+        let ticket = state.redeem_ws_ticket(&ws_ticket).expect("fresh ticket redeems");
+        assert_eq!(ticket.player_id, host_id);
+
+        let (tx, mut rx) = state::connection_channel();
+        let (mut player_id, mut room_code) = (None, None);
+        websocket::rooms::handle_join_with_ticket(&state, &ticket.room_code, ticket.player_id, &tx, &mut player_id, &mut room_code).await;
+        assert_eq!(player_id, Some(host_id));
+        assert!(state.connections.contains_key(&host_id));
+        let Some(Message::Text(first)) = rx.recv().await else { panic!("expected a message") };
+        assert!(matches!(serde_json::from_str(&first).unwrap(), ServerMessage::PlayerJoined { .. }));
+
+        // Single use: the same ticket is refused the second time
+        assert!(state.redeem_ws_ticket(&ws_ticket).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_join_finished_room_is_rejected() {
+        let state = AppState::new();
+        let room_code = create(&state, "host").await.room.unwrap().code;
+        state.with_room_mut(&room_code, |room| room.game_state = GameState::Finished);
+
+        let response = join(&state, &room_code, "late").await;
+        assert!(!response.success);
+        assert_eq!(response.message, "Game has already finished");
+        assert_eq!(state.get_room(&room_code).unwrap().players.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_room_events_hidden_unless_debug_enabled() {
+        let state = AppState::new();
+        let room_code = create(&state, "host").await.room.unwrap().code;
+        let (status, _) = get_room_events(
+            axum::extract::State(state.clone()),
+            axum::extract::Path(room_code),
+        ).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_full_room_dump_requires_debug_secret() {
+        let state = AppState::with_config(ServerConfig { debug_secret: Some("s3cret".to_string()), ..ServerConfig::default() });
+        let room_code = create(&state, "host").await.room.unwrap().code;
+        state.with_room_mut(&room_code, |room| room.word = Some("apple".to_string()));
+        let dump = |secret: Option<&str>| {
+            let mut headers = axum::http::HeaderMap::new();
+            if let Some(secret) = secret {
+                headers.insert("x-debug-secret", secret.parse().unwrap());
+            }
+            get_room_full(State(state.clone()), axum::extract::Path(room_code.clone()), headers)
+        };
+
+        assert_eq!(dump(None).await.0, StatusCode::NOT_FOUND);
+        assert_eq!(dump(Some("wrong")).await.0, StatusCode::NOT_FOUND);
+        let (status, Json(body)) = dump(Some("s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["room"]["word"], "apple");
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_at_room_cap() {
+        let state = AppState::with_config(ServerConfig { max_rooms: 1, ..ServerConfig::default() });
+        let (status, _) = health_ready(axum::extract::State(state.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+
+        create(&state, "host").await;
+        let (status, _) = health_ready(axum::extract::State(state.clone())).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!create(&state, "other").await.success);
+    }
+
+    #[tokio::test]
+    async fn test_get_room_player_present_and_absent() {
+        let state = AppState::new();
+        let created = create(&state, "host").await;
+        let room_code = created.room.unwrap().code;
+        let host_id = created.player.unwrap().id;
+
+        let (status, Json(body)) = get_room_player(
+            axum::extract::State(state.clone()),
+            axum::extract::Path((room_code.clone(), host_id.to_string())),
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["player"]["username"], "host");
+        assert_eq!(body["player"]["score"], 0);
+
+        let (status, _) = get_room_player(
+            axum::extract::State(state.clone()),
+            axum::extract::Path((room_code, Uuid::new_v4().to_string())),
+        ).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_presence_lists_graced_player_as_disconnected() {
+        let state = AppState::new();
+        let created = create(&state, "host").await;
+        let room_code = created.room.unwrap().code;
+        let joined = join(&state, &room_code, "guest").await;
+        let guest_id = joined.player.unwrap().id;
+        let token = joined.auth_token.unwrap();
+        let (tx, _rx) = state::connection_channel();
+        let (mut player_id, mut current_room) = (None, None);
+        websocket::rooms::handle_join_room(&state, &room_code, "guest", &token, &tx, &mut player_id, &mut current_room).await;
+
+        websocket::rooms::handle_disconnect(&state, guest_id, Some(&room_code)).await;
+
+        let (status, Json(body)) = get_room_presence(State(state.clone()), axum::extract::Path(room_code)).await;
+        assert_eq!(status, StatusCode::OK);
+        let guest = body["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["id"] == guest_id.to_string())
+            .expect("graced player is still listed");
+        assert_eq!(guest["is_connected"], false);
+    }
+
+    #[tokio::test]
+    async fn test_room_scoring_reflects_custom_config() {
+        let state = AppState::new();
+        let room_code = create(&state, "host").await.room.unwrap().code;
+        let scoring_of = |room_code: String| get_room_scoring(State(state.clone()), axum::extract::Path(room_code));
+
+        let (status, Json(body)) = scoring_of(room_code.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["scoring"]["pmax"], crate::scoring::SCORING_CONSTANTS.pmax);
+
+        state.with_room_mut(&room_code, |room| {
+            room.scoring.pmax = 900;
+            room.scoring.early_finish_bonus = 40;
+        });
+        let (_, Json(body)) = scoring_of(room_code.to_lowercase()).await;
+        assert_eq!(body["scoring"]["pmax"], 900);
+        assert_eq!(body["scoring"]["early_finish_bonus"], 40);
+        assert_eq!(body["scoring"]["tie_window_ms"], crate::scoring::SCORING_CONSTANTS.tie_window_ms);
+
+        let (status, _) = scoring_of("NOPE00".to_string()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_host_leaving_via_rest_transfers_host() {
+        let state = AppState::new();
+        let host = create(&state, "host").await;
+        let room_code = host.room.unwrap().code;
+        let host_id = host.player.unwrap().id;
+        let guest = join(&state, &room_code, "guest").await;
+        let guest_id = guest.player.unwrap().id;
+        let mut rx = test_support::connect(&state, &room_code, guest_id);
+
+        let status = leave(&state, &room_code, host_id, &host.auth_token.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        assert_eq!(state.get_room(&room_code).unwrap().host_id, guest_id);
+        assert!(test_support::drain(&mut rx)
+            .iter()
+            .any(|m| matches!(m, ServerMessage::HostChanged { new_host } if new_host.id == guest_id)));
+    }
+
+    #[tokio::test]
+    async fn test_overflow_joiner_becomes_spectator() {
+        let state = AppState::new();
+        let room_code = create(&state, "host").await.room.unwrap().code;
+        state.with_room_mut(&room_code, |room| {
+            room.max_players = 2;
+            room.max_spectators = 1;
+        });
+
+        assert!(!join(&state, &room_code, "second").await.spectator);
+        let overflow = join(&state, &room_code, "third").await;
+        assert!(overflow.success && overflow.spectator);
+        assert_eq!(overflow.player.unwrap().state, PlayerState::Spectator);
+        assert!(!join(&state, &room_code, "fourth").await.success);
+    }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 // Game state enum
@@ -19,6 +19,50 @@ pub enum PlayerState {
     Disconnected,
 }
 
+// Why the drawer changed, sent with DrawerChanged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DrawerChangeReason {
+    Rotation,   // Normal end of round
+    Disconnect, // Drawer's connection dropped mid-round
+    Skip,       // Round skipped before it finished
+    GiveUp,     // Drawer gave up their turn
+}
+
+// How a client should treat an Error: Info and Warning leave the connection usable,
+// Fatal is followed by the server closing it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ErrorSeverity {
+    Info,    // Nothing went wrong on the client's side, e.g. a chat message held back
+    Warning, // The request was refused; the client can correct it or ignore it
+    Fatal,   // The player has lost their place, e.g. kicked; the socket closes next
+}
+
+// When a finished round's drawing is wiped from the canvas
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ClearTiming {
+    #[default]
+    RoundEnd,   // As soon as the round ends
+    WordSelect, // Once the next drawer picks a word, so the drawing stays up through the intermission
+}
+
+// How forgiving guess matching is
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum GuessMatching {
+    #[default]
+    Strict,  // Case and surrounding whitespace are ignored, nothing else
+    Lenient, // Also folds look-alike letters (Cyrillic 'а', full-width 'ａ') to ASCII
+}
+
+// Why a WebSocket JoinRoom was refused, sent with JoinError
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum JoinErrorCode {
+    RoomNotFound,
+    RoomFull,         // Full, and the player never registered through /joinRoom
+    NotRegistered,    // Room has space but the player has to join through /joinRoom first
+    AlreadyConnected, // Another connection is already attached to this player
+    InvalidToken,     // The auth token wasn't issued to the player with this username
+}
+
 // Color enum for drawing
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Color {
@@ -53,6 +97,39 @@ pub struct Player {
     pub is_drawing: bool,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub artist_streak: u32, // Track artist streak across rounds (0-5)
+    pub guess_streak: u32, // Consecutive rounds this player guessed the word (reset on a miss)
+    pub ready: bool, // Lobby ready flag; only enforced in rooms that require it, cleared when a game ends
+    // Set while the player's socket is gone; they keep their seat and score until the grace window ends
+    pub disconnected_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Player {
+    // Create a freshly joined player with a new ID and no score
+    pub fn new(username: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            score: 0,
+            state: PlayerState::Guessing,
+            is_connected: true,
+            is_drawing: false,
+            joined_at: chrono::Utc::now(),
+            artist_streak: 0,
+            guess_streak: 0,
+            ready: false,
+            disconnected_at: None,
+        }
+    }
+
+    // All score changes go through these two so a score never wraps past either end
+    pub fn add_score(&mut self, points: u32) {
+        self.score = self.score.saturating_add(points);
+    }
+
+    // Penalties stop at zero rather than underflowing
+    pub fn deduct_score(&mut self, points: u32) {
+        self.score = self.score.saturating_sub(points);
+    }
 }
 
 // Drawing stroke for canvas
@@ -89,6 +166,36 @@ pub struct DrawPath {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+// A finished round's drawing, kept for the public gallery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryEntry {
+    pub round_number: u32,
+    pub cycle_number: u32,
+    pub word: String,
+    pub drawer_id: Uuid,
+    pub paths: Vec<DrawPath>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Something that happened in a room, kept for debugging desyncs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum RoomEventKind {
+    PlayerJoined { player_id: Uuid, username: String },
+    PlayerLeft { player_id: Uuid },
+    GameStarted { drawer_id: Uuid },
+    WordSelected { drawer_id: Uuid }, // The word itself is deliberately not logged
+    RoundEnded { round_number: u32, word: String },
+    DrawerChanged { drawer_id: Uuid, reason: DrawerChangeReason },
+    ScoreAdjusted { by: Uuid, player_id: Uuid, delta: i64, new_score: u32 }, // Host correction, delta as applied
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomEvent {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub event: RoomEventKind,
+}
+
 // Chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -98,6 +205,8 @@ pub struct ChatMessage {
     pub message: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub is_winners_only: bool,
+    #[serde(default)]
+    pub is_spectator: bool, // Sent by a spectator; never evaluated as a guess
 }
 
 // Guess tracking for scoring
@@ -123,6 +232,37 @@ pub struct RoundScores {
     pub correct_guesses: Vec<Guess>,
     pub median_guess_time: f64, // Median of normalized times
     pub fraction_guessed: f64,  // G/N
+    pub guess_streaks: HashMap<Uuid, u32>, // Player ID -> guess streak after this round
+}
+
+// A player's final standing; tied scores share a rank and the next rank skips (1, 1, 3)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FinalRanking {
+    pub player_id: Uuid,
+    pub username: String,
+    pub score: u32,
+    pub rank: u32,
+}
+
+// One player's finalized score change from a round
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreChange {
+    pub player_id: Uuid,
+    pub new_score: u32,
+    pub delta: u32,
+}
+
+// Result of ending a round, computed atomically by AppState::end_round
+#[derive(Debug, Clone)]
+pub struct RoundOutcome {
+    pub scores: RoundScores,
+    pub next_drawer: Option<Player>, // None when the game is over
+    pub game_over: bool,
+    pub final_scores: HashMap<String, u32>,
+    pub rankings: Vec<FinalRanking>,
+    pub word_choices: Vec<String>, // Offered privately to the next drawer
+    pub score_changes: Vec<ScoreChange>, // Everyone who was scored this round
+    pub sudden_death_players: Vec<Uuid>, // Non-empty when the next round is a sudden-death tiebreak
 }
 
 // Game room struct
@@ -133,28 +273,280 @@ pub struct Room {
     pub host_id: Uuid,                   // ID of the host/creator
     pub players: HashMap<Uuid, Player>,  // Player ID -> Player
     pub current_drawer: Option<Uuid>,    // ID of player currently drawing
+    pub draw_order: Vec<Uuid>,           // Drawing rotation fixed at game start; late joiners append
     pub word: Option<String>,            
+    #[serde(skip)]
+    pub pending_word_choices: Vec<String>, // Words offered to the drawer; never sent in room state
+    pub word_choices_rerolled: bool,     // The drawer has used this round's one reroll
+    #[serde(skip)]
+    pub custom_words: Vec<String>,       // Host-supplied word list; empty means the bundled bank
+    pub min_word_length: u32,            // Shorter words are never offered
     pub round_number: u32,
     pub max_rounds: u32, // Maximum number of cycles (complete rotations through all players)
     pub cycle_number: u32, // Track how many times we've gone through all players
+    pub rounds_remaining: u32, // Rounds still to play after the current one; refreshed as rounds change
+    pub rounds_played: u32,    // Rounds finished this game, skipped ones included
+    pub used_words: Vec<String>, // Words drawn this game, in order, for the post-game recap
     pub round_duration: u32,             
+    pub guess_delay_secs: u32,           // Warmup at round start during which guesses don't count
+    pub show_pattern_after: f64,         // Fraction of the round before guessers get the word's pattern
+    pub guess_matching: GuessMatching,
+    pub hint_count: u32,                 // Letters revealed to guessers over the back half of each round
+    pub start_reveal: u32,               // Letters already shown in the first pattern; share max_hint_reveal with hint_count
+    pub final_round_multiplier: f64,     // Applied to all scores during the last cycle (1.0 = off)
+    pub warmup_first_round: bool,        // First cycle is played for fun and scores nothing
+    pub sudden_death: bool,              // Settle a first-place tie with one extra round
+    pub sudden_death_players: Vec<Uuid>, // Tied leaders contesting the current sudden-death round
+    pub require_ready: bool,             // StartGame waits until every active player is ready
+    pub play_again_votes: Vec<Uuid>,     // Players who want a rematch after the game finished
+    pub max_game_duration_secs: Option<u32>, // Wall-clock time box; the game ends when it runs out, even mid-cycle
+    pub game_started_at: Option<chrono::DateTime<chrono::Utc>>, // With the limit above, lets clients show a game clock
+    pub practice: bool,                  // Solo room: words loop forever and nothing is scored
+    pub persistent: bool,                // Lobby that outlives its last player, reset to Waiting instead of deleted
     pub game_state: GameState,
     pub round_start_time: Option<chrono::DateTime<chrono::Utc>>,
     pub round_end_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub paused_remaining_ms: Option<u64>, // Set while the round is frozen waiting for its drawer to come back
     pub drawing_paths: Vec<DrawPath>,    // All drawing paths in current round
+    pub clear_canvas_on: ClearTiming,    // Until then a finished round's paths stay here
+    pub max_undo: Option<u32>,           // Undo steps allowed per round; None = unlimited
+    pub canvas_width: u32,               // Canvas size every client draws on; strokes are clamped to it
+    pub canvas_height: u32,
+    pub undos_used: u32,                 // Undo steps taken this round
+    pub min_draw_time_before_skip: u32,  // Seconds into a round before it can be skipped or given up
+    pub skip_votes: Vec<Uuid>,           // Guessers who voted to skip this round
+    pub hint_cost: u32,                  // Points a guesser pays for one privately revealed letter
+    pub max_hints_per_round: u32,        // Hints each guesser may buy per round (0 disables buying)
+    #[serde(skip)]
+    pub hint_purchases: HashMap<Uuid, Vec<usize>>, // Letter positions each guesser has bought this round
+    #[serde(skip)]
+    pub gallery: Vec<GalleryEntry>,      // Finished rounds' drawings (served by /gallery, not room state)
     pub chat_messages: Vec<ChatMessage>, // Chat history (keep last 10 between rounds)
     pub current_round_guesses: Vec<Guess>, // Track guesses for current round scoring
     pub winners: Vec<Uuid>, // Players who have guessed correctly (including artist)
-    pub max_players: u8,
+    pub scoring: crate::scoring::ScoringConfig,
+    pub round_token: u64, // Bumped whenever a round ends so stale timers/guesses can't end the next one
+    pub max_players: u8,                 // Cap on active (non-spectator) players
+    pub allow_spectators: bool,          // Joiners beyond max_players become spectators instead of being turned away
+    pub max_spectators: u8,
+    pub allow_spectator_chat: bool,      // Off: spectators watch silently
+    pub muted: HashSet<Uuid>,            // Players the host has silenced; their guesses still count
+    pub winners_chat_cap: u32,           // Winners-chat messages each player may send per round (0 = unlimited)
+    #[serde(skip)]
+    pub winners_chat_sent: HashMap<Uuid, u32>, // Winners-chat messages each player has sent this round
+    #[serde(skip)]
+    pub near_miss_streaks: HashMap<Uuid, u32>, // Consecutive close-but-wrong guesses per player this round
+    pub reveal_drawer: bool,             // Off for "blind artist" games: guessers aren't told who's drawing
+    pub show_draw_order: bool,           // Broadcast the turn order at game start and as it changes
+    pub drawer_sees_guesses: bool,       // Off: the drawer gets no public chat while they're drawing
+    pub verifiable_words: bool,          // Commit to a word seed at game start and reveal it at the end
+    #[serde(skip)]
+    pub word_seed: Option<[u8; 32]>,     // This game's committed seed; secret until the game ends
+    #[serde(skip)]
+    pub word_draws: u64,                 // Word draws made from the seed so far
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl Room {
+    // Whether the current cycle is an unscored warmup
+    pub fn is_warmup_round(&self) -> bool {
+        self.warmup_first_round && self.cycle_number <= 1
+    }
+
+    // Score multiplier for rounds in the current cycle; only the last cycle is boosted,
+    // and a warmup cycle scores nothing
+    pub fn score_multiplier(&self) -> f64 {
+        if self.is_warmup_round() {
+            0.0
+        } else if self.cycle_number >= self.max_rounds {
+            self.final_round_multiplier
+        } else {
+            1.0
+        }
+    }
+
+    // Players who could still guess this round: connected, not drawing, not spectating
+    pub fn potential_guessers(&self) -> usize {
+        self.players
+            .values()
+            .filter(|p| Some(p.id) != self.current_drawer)
+            .filter(|p| p.is_connected && p.state != PlayerState::Spectator)
+            .count()
+    }
+
+    // Who has guessed this round (the drawer excluded) and how many potential guessers haven't
+    pub fn guess_progress(&self) -> (Vec<Uuid>, usize) {
+        let guessed: Vec<Uuid> = self
+            .winners
+            .iter()
+            .filter(|id| Some(**id) != self.current_drawer)
+            .copied()
+            .collect();
+        let remaining = self.potential_guessers().saturating_sub(guessed.len());
+        (guessed, remaining)
+    }
+
+    // Seconds left before the current round may be skipped, or None once skipping is open.
+    // A drawer still choosing a word hasn't started drawing, so the full wait applies.
+    pub fn skip_cooldown_remaining(&self, now: chrono::DateTime<chrono::Utc>) -> Option<u32> {
+        let elapsed = self
+            .round_start_time
+            .map(|start| now.signed_duration_since(start).num_seconds().max(0) as u32)
+            .unwrap_or(0);
+        let remaining = self.min_draw_time_before_skip.saturating_sub(elapsed);
+        (remaining > 0).then_some(remaining)
+    }
+
+    // Active players, i.e. everyone except spectators
+    pub fn active_player_count(&self) -> usize {
+        self.players.values().filter(|p| p.state != PlayerState::Spectator).count()
+    }
+
+    // Active players with a live WebSocket; only they count towards starting a game
+    pub fn connected_player_count(&self) -> usize {
+        self.players
+            .values()
+            .filter(|p| p.is_connected && p.state != PlayerState::Spectator)
+            .count()
+    }
+
+    pub fn spectator_count(&self) -> usize {
+        self.players.len() - self.active_player_count()
+    }
+
+    // Active players ordered by join time, used to seed the draw order at game start
+    pub fn players_by_join_time(&self) -> Vec<Uuid> {
+        let mut ordered: Vec<&Player> = self.players.values().filter(|p| p.state != PlayerState::Spectator).collect();
+        ordered.sort_by_key(|p| p.joined_at);
+        ordered.into_iter().map(|p| p.id).collect()
+    }
+
+    // No room for another player, nor for a spectator
+    pub fn is_full(&self) -> bool {
+        self.active_player_count() >= self.max_players as usize
+            && (!self.allow_spectators || self.spectator_count() >= self.max_spectators as usize)
+    }
+
+    // Rounds left after the current one: the rest of this cycle plus every remaining full cycle.
+    // A cycle is one turn for each player in the draw order; sudden death isn't counted.
+    pub fn rounds_after_current(&self) -> u32 {
+        if self.cycle_number > self.max_rounds {
+            return 0;
+        }
+        let per_cycle = self.draw_order.len() as u32;
+        let later_cycles = self.max_rounds.saturating_sub(self.cycle_number);
+        later_cycles * per_cycle + per_cycle.saturating_sub(self.round_number)
+    }
+
+    // Whether the game has run past its wall-clock limit
+    pub fn game_time_exceeded(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match (self.max_game_duration_secs, self.game_started_at) {
+            (Some(limit), Some(started)) => now >= started + chrono::Duration::seconds(limit as i64),
+            _ => false,
+        }
+    }
+
+    // Whether every connected active player has marked themselves ready
+    pub fn all_ready(&self) -> bool {
+        self.players
+            .values()
+            .filter(|p| p.is_connected && p.state != PlayerState::Spectator)
+            .all(|p| p.ready)
+    }
+
+    // Active players sharing the top score, in join order; empty unless at least two are tied
+    pub fn tied_leaders(&self) -> Vec<Uuid> {
+        let active = self.players_by_join_time();
+        let top = active.iter().filter_map(|id| self.players.get(id)).map(|p| p.score).max();
+        let tied: Vec<Uuid> = active
+            .into_iter()
+            .filter(|id| self.players.get(id).map(|p| p.score) == top)
+            .collect();
+        if tied.len() >= 2 { tied } else { Vec::new() }
+    }
+
+    // Guessing opens once the warmup (guess_delay_secs) after the round start has passed
+    pub fn guessing_open(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match self.round_start_time {
+            Some(start) => now >= start + chrono::Duration::seconds(self.guess_delay_secs as i64),
+            None => true,
+        }
+    }
+}
+
+// Room settings a host can change; omitted fields are left untouched
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RoomSettingsUpdate {
+    #[serde(default)]
+    pub max_rounds: Option<u32>,
+    #[serde(default)]
+    pub max_players: Option<u8>,
+    #[serde(default)]
+    pub guess_delay_secs: Option<u32>,
+    #[serde(default)]
+    pub show_pattern_after: Option<f64>,
+    #[serde(default)]
+    pub hint_count: Option<u32>,
+    #[serde(default)]
+    pub start_reveal: Option<u32>,
+    #[serde(default)]
+    pub final_round_multiplier: Option<f64>,
+    #[serde(default)]
+    pub max_undo: Option<u32>, // Once set a room keeps a limit; there is no way back to unlimited
+    #[serde(default)]
+    pub canvas_width: Option<u32>,
+    #[serde(default)]
+    pub canvas_height: Option<u32>,
+    #[serde(default)]
+    pub min_draw_time_before_skip: Option<u32>,
+    #[serde(default)]
+    pub allow_spectators: Option<bool>,
+    #[serde(default)]
+    pub max_spectators: Option<u8>,
+    #[serde(default)]
+    pub allow_spectator_chat: Option<bool>,
+    #[serde(default)]
+    pub winners_chat_cap: Option<u32>,
+    #[serde(default)]
+    pub clear_canvas_on: Option<ClearTiming>,
+    #[serde(default)]
+    pub guess_matching: Option<GuessMatching>,
+    #[serde(default)]
+    pub reveal_drawer: Option<bool>,
+    #[serde(default)]
+    pub show_draw_order: Option<bool>,
+    #[serde(default)]
+    pub drawer_sees_guesses: Option<bool>,
+    #[serde(default)]
+    pub verifiable_words: Option<bool>, // Takes effect from the next game start
+    #[serde(default)]
+    pub warmup_first_round: Option<bool>,
+    #[serde(default)]
+    pub sudden_death: Option<bool>,
+    #[serde(default)]
+    pub require_ready: Option<bool>,
+    #[serde(default)]
+    pub max_game_duration_secs: Option<u32>, // 0 removes the limit
+    #[serde(default)]
+    pub hint_cost: Option<u32>,
+    #[serde(default)]
+    pub max_hints_per_round: Option<u32>,
+}
+
 // Request/Response structs for API endpoints
 #[derive(Debug, Deserialize)]
 pub struct CreateRoomRequest {
     pub username: String,
     pub round_duration: u32,
+    #[serde(default)]
+    pub custom_words: Option<Vec<String>>,
+    #[serde(default)]
+    pub min_word_length: Option<u32>,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default)]
+    pub persistent: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -163,6 +555,37 @@ pub struct CreateRoomResponse {
     pub message: String,
     pub room: Option<Room>,
     pub player: Option<Player>,
+    pub auth_token: Option<String>, // Required on player-identifying REST calls
+}
+
+// POST /rooms: the created room plus a one-shot ticket for the /ws upgrade
+#[derive(Debug, Serialize)]
+pub struct CreateRoomWithTicketResponse {
+    #[serde(flatten)]
+    pub created: CreateRoomResponse,
+    pub ws_ticket: Option<String>,
+}
+
+// Dry run of the custom word list checks create_room applies
+#[derive(Debug, Deserialize)]
+pub struct ValidateWordsRequest {
+    pub words: Vec<String>,
+    #[serde(default)]
+    pub min_word_length: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct WordRejection {
+    pub word: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidateWordsResponse {
+    pub accepted: Vec<String>,
+    pub rejected: Vec<WordRejection>,
+    pub usable_count: usize,
+    pub enough: bool, // Whether create_room would accept this list
 }
 
 #[derive(Debug, Deserialize)]
@@ -177,12 +600,35 @@ pub struct JoinRoomResponse {
     pub message: String,
     pub room: Option<Room>,
     pub player: Option<Player>,
+    pub auth_token: Option<String>, // Required on player-identifying REST calls
+    pub spectator: bool, // The room was full, so the join landed as a spectator
 }
 
 #[derive(Debug, Deserialize)]
 pub struct LeaveRoomRequest {
     pub room_code: String,
     pub player_id: String,
+    pub auth_token: String,
+}
+
+// Query string on the /ws upgrade; older clients send no protocol_version
+#[derive(Debug, Deserialize)]
+pub struct WsConnectQuery {
+    #[serde(default)]
+    pub protocol_version: Option<u32>,
+    #[serde(default)]
+    pub observe: Option<String>, // Room code to watch as an admin observer
+    #[serde(default)]
+    pub admin_token: Option<String>,
+    #[serde(default)]
+    pub ws_ticket: Option<String>, // From POST /rooms; binds the socket to that player without JoinRoom
+}
+
+// Identifies the caller on read-only REST endpoints
+#[derive(Debug, Deserialize)]
+pub struct PlayerAuthQuery {
+    pub player_id: Option<String>,
+    pub auth_token: Option<String>,
 }
 
 // Frontend drawing path format (simplified)
@@ -206,42 +652,139 @@ pub struct FrontendDrawStroke {
     pub brush_px: u32,
 }
 
-// WebSocket message types
+// WebSocket message types; unknown fields are rejected so client typos surface as errors
+// (UpdateSettings flattens its settings, so it still ignores extras)
 #[derive(Debug, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", deny_unknown_fields)]
 pub enum ClientMessage {
-    JoinRoom { room_code: String, username: String },
+    JoinRoom {
+        room_code: String,
+        username: String,
+        #[serde(default)]
+        auth_token: String, // From /joinRoom or /createRoom; proves the socket is that player
+    },
     LeaveRoom { room_code: String, player_id: String },
     DrawUpdate { room_code: String, path: FrontendDrawPath },
     DrawStroke { room_code: String, stroke: FrontendDrawStroke },
     Chat { room_code: String, message: String },
     WinnersChat { room_code: String, message: String },
     Guess { room_code: String, guess: String },
-    StartGame { room_code: String },
+    StartGame {
+        room_code: String,
+        #[serde(default)]
+        force: bool, // Host only: start even if not everyone is ready
+    },
+    SetReady { room_code: String, ready: bool },
+    TransferHost { room_code: String, new_host_id: Uuid }, // Current host only
+    MutePlayer { room_code: String, target_player_id: Uuid, muted: bool }, // Host only
+    KickPlayer { room_code: String, target_player_id: Uuid }, // Host only
+    AdjustScore { room_code: String, player_id: Uuid, delta: i64 }, // Host only; a score never drops below zero
     EndRound { room_code: String },
     WordSelected { room_code: String, word: String },
-    UpdateSettings { room_code: String, max_rounds: u32 },
+    Undo { room_code: String },
+    ClearCanvas { room_code: String }, // Drawer wipes every path drawn this round
+    NextWord { room_code: String }, // Practice rooms only: skip straight to new word choices
+    VoteSkip { room_code: String },
+    GiveUp { room_code: String }, // Drawer hands over their turn
+    RequestScoreboard { room_code: String },
+    RequestChatHistory { room_code: String }, // e.g. after a reconnect
+    PlayAgain { room_code: String }, // Vote for a rematch once the game has finished
+    BuyHint { room_code: String }, // Guesser pays hint_cost for one letter of the word
+    RerollWords { room_code: String }, // Drawer swaps their word choices for fresh ones, once per round
+    // Re-bind a reloaded page to its player using the auth token issued at create/join
+    Reconnect { room_code: String, player_id: String, reconnect_token: String },
+    UpdateSettings {
+        room_code: String,
+        #[serde(flatten)]
+        settings: RoomSettingsUpdate,
+    },
 }
 
+// Messages are serialized as soon as they're built, so variant size doesn't matter here
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ServerMessage {
     PlayerJoined { room_code: String, player: Player },
     PlayerLeft { room_code: String, player: Player },
+    PlayerStateChanged { player_id: Uuid, is_connected: bool, state: PlayerState }, // Same view as GET /rooms/:code/presence
     DrawUpdate { room_code: String, path: DrawPath },
     DrawStroke { room_code: String, stroke: DrawStroke },
     ChatMessage { message: ChatMessage },
     CorrectGuess { player: Player, word: String },
+    FirstBlood { player: Player }, // First correct guess of the round; sent alongside CorrectGuess
+    DrawOrder { order: Vec<Uuid> }, // This game's turn rotation, for "up next" indicators
+    DrawerDisconnected { drawer_id: Uuid, grace_secs: u64 }, // Round clock frozen; the turn passes on if they're not back in time
+    DrawerReconnected { drawer_id: Uuid, round_time_remaining_secs: u32 }, // Round clock running again
+    SeedCommitment { seed_hash: String }, // Verifiable rooms, at game start: hex SHA-256 of the word seed
+    SeedReveal { seed: String },          // At game end: the hex seed, so players can replay the word draws
     RoundScores { scores: RoundScores }, // Detailed scoring results
     GameStarted { room_code: String, drawer: Player },
     PlayerKicked { room_code: String, player: Player },
     RoundEnd { word: String, scores: HashMap<String, u32> },
-    GameEnded { final_scores: HashMap<String, u32> },
-    RoundStart { room_code: String, drawer: Player },
+    GameEnded {
+        final_scores: HashMap<String, u32>, // Kept for older clients
+        rankings: Vec<FinalRanking>,
+        total_rounds_played: u32,
+        cycles_completed: u32,
+        words_used: Vec<String>,
+    },
+    // multiplier > 1 on the boosted final cycle and 0 during a warmup; drawer is omitted
+    // for guessers when the room hides it
+    RoundStart {
+        room_code: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        drawer: Option<Player>,
+        score_multiplier: f64,
+        warmup: bool,
+        round_number: u32,
+        cycle_number: u32,
+        max_rounds: u32, // Cycles in the game
+        round_duration: u32,
+        rounds_remaining: u32, // After this one
+    },
     GameStateUpdate { room: Room },
     HostChanged { new_host: Player },
-    Error { message: String },
+    Error { message: String, severity: ErrorSeverity },
     WordSelected { word: String },
+    WordHint { pattern: String }, // Blanked-out word for guessers: "_" per letter, spaces kept
+    CloseGuess { guess: String, streak: u32, message: String }, // Private nudge after a near miss
+    WordChoices { words: Vec<String> }, // Sent only to the drawer
+    DrawerChanged {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        drawer: Option<Player>,
+        reason: DrawerChangeReason,
+    },
+    CanvasSyncChunk { index: u32, total: u32, paths: Vec<DrawPath> }, // Canvas for a (re)joining client, in order
+    AllGuessed { word: String, advance_in_ms: u64 }, // Everyone got it; the round ends after the delay
+    PathUndone { room_code: String, path_id: Uuid }, // Drawer removed their most recent path
+    CanvasCleared { room_code: String },
+    // Everything a reconnecting client needs in one message: the room as they may see it
+    // (canvas and chat history included), their word choices if they're picking, and the clock
+    FullSync { room: Room, word_choices: Vec<String>, round_time_remaining_secs: Option<u32> },
+    // Finalized points from the round that just ended, one per scored player (guesses aren't scored provisionally)
+    ScoreUpdate { player_id: Uuid, new_score: u32, delta: i64 }, // Negative only for host corrections
+    // Complete end-of-round picture, sent once before the next round; next_drawer is None when the game is over
+    RoundSummary { word: String, scores: RoundScores, next_drawer: Option<Player>, intermission_secs: u32 },
+    Scoreboard { rankings: Vec<FinalRanking> }, // Current standings, sent only to the requester
+    ChatHistory { messages: Vec<ChatMessage> }, // Recent chat as the requester may see it
+    // After GameEnded: the room stays open for a rematch vote, and closes if nobody votes in time
+    PostGame { rankings: Vec<FinalRanking>, play_again_timeout_secs: u64 },
+    PlayAgainUpdate { votes: Vec<Uuid>, needed: usize }, // needed counts the votes for a majority
+    RoomClosed { room_code: String, reason: String },
+    GuessProgress { guessed: Vec<Uuid>, remaining: usize }, // Who has guessed so far; never the word
+    // The game ended tied for first: only these players guess next round and the first to get it wins
+    SuddenDeath { players: Vec<Uuid> },
+    ReadyUpdate { player_id: Uuid, ready: bool },
+    PlayerMuted { player_id: Uuid, muted: bool },
+    HintReveal { index: usize, letter: char, cost: u32 }, // Bought letter, sent only to the buyer
+    JoinError { error_code: JoinErrorCode, message: String },
+    // Countdown while the drawer picks a word; drawer is omitted for guessers when the room hides it
+    SelectingWord {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        drawer: Option<Player>,
+        seconds_remaining: u32,
+    },
 }
 
 // Health check response
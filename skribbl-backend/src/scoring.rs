@@ -1,9 +1,10 @@
-use crate::models::{Guess, RoundScores};
+use crate::models::{FinalRanking, Guess, Player, Room, RoundScores};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-// Scoring system constants
-pub const SCORING_CONSTANTS: ScoringConstants = ScoringConstants {
+// Default scoring system constants
+pub const SCORING_CONSTANTS: ScoringConfig = ScoringConfig {
     pmax: 500,
     pmin: 100,
     base: 320,
@@ -12,9 +13,14 @@ pub const SCORING_CONSTANTS: ScoringConstants = ScoringConstants {
     tie_window_ms: 200,
     streak_bonus_per_tier: 50,
     max_streak: 5,
+    guess_streak_bonus: 0,
+    early_finish_bonus: 0,
+    artist_streak_threshold: 0.5,
 };
 
-pub struct ScoringConstants {
+// Scoring parameters in effect for a room
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoringConfig {
     pub pmax: u32,
     pub pmin: u32,
     pub base: u32,
@@ -23,6 +29,15 @@ pub struct ScoringConstants {
     pub tie_window_ms: u64,
     pub streak_bonus_per_tier: u32,
     pub max_streak: u32,
+    pub guess_streak_bonus: u32, // Per consecutive guessed round beyond the first (0 disables)
+    pub early_finish_bonus: u32, // Max artist bonus for a round everyone guessed, scaled by time left (0 disables)
+    pub artist_streak_threshold: f64, // Fraction of guessers who must guess by halfway to grow the artist streak
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        SCORING_CONSTANTS
+    }
 }
 
 /// Calculate scores for a round based on the scoring system
@@ -33,6 +48,7 @@ pub fn calculate_round_scores(
     correct_guesses: Vec<Guess>,
     potential_guessers: u32,
     artist_streak: u32,
+    config: &ScoringConfig,
 ) -> RoundScores {
     let mut scores = RoundScores {
         round_number,
@@ -44,6 +60,7 @@ pub fn calculate_round_scores(
         correct_guesses: correct_guesses.clone(),
         median_guess_time: 0.0,
         fraction_guessed: 0.0,
+        guess_streaks: HashMap::new(),
     };
 
     // Handle zero-guess rounds
@@ -64,14 +81,14 @@ pub fn calculate_round_scores(
         .collect();
     normalized_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
     let median_index = normalized_times.len() / 2;
-    scores.median_guess_time = if normalized_times.len() % 2 == 0 {
+    scores.median_guess_time = if normalized_times.len().is_multiple_of(2) {
         (normalized_times[median_index - 1] + normalized_times[median_index]) / 2.0
     } else {
         normalized_times[median_index]
     };
 
     // Calculate guesser scores
-    let guesser_scores = calculate_guesser_scores(&correct_guesses, round_duration, potential_guessers);
+    let guesser_scores = calculate_guesser_scores(&correct_guesses, round_duration, potential_guessers, config);
     scores.guesser_scores = guesser_scores;
 
     // Calculate artist score
@@ -81,16 +98,35 @@ pub fn calculate_round_scores(
         scores.median_guess_time,
         *top_guesser_score,
         artist_streak,
+        config,
     );
 
+    // A round everyone guessed quickly signals a clear drawing; this sits on top of the cap
+    if f >= 1.0 {
+        scores.artist_score += calculate_early_finish_bonus(&correct_guesses, round_duration, config);
+    }
+
     scores
 }
 
+/// Artist bonus for how early the last needed guess landed, from that guess's time remaining
+fn calculate_early_finish_bonus(correct_guesses: &[Guess], round_duration: u32, config: &ScoringConfig) -> u32 {
+    let Some(last_guess) = correct_guesses.iter().max_by_key(|guess| guess.timestamp) else {
+        return 0;
+    };
+    if round_duration == 0 {
+        return 0;
+    }
+    let remaining = (last_guess.time_remaining as f64 / round_duration as f64).clamp(0.0, 1.0);
+    (config.early_finish_bonus as f64 * remaining).round() as u32
+}
+
 /// Calculate individual guesser scores
 fn calculate_guesser_scores(
     correct_guesses: &[Guess],
     _round_duration: u32,
     _potential_guessers: u32,
+    config: &ScoringConfig,
 ) -> HashMap<Uuid, u32> {
     let mut scores = HashMap::new();
     
@@ -98,16 +134,16 @@ fn calculate_guesser_scores(
         return scores;
     }
 
-    // Sort guesses by timestamp (earliest first)
+    // Sort guesses by timestamp (earliest first); player ID keeps identical timestamps in a fixed order
     let mut sorted_guesses: Vec<&Guess> = correct_guesses.iter().collect();
-    sorted_guesses.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    sorted_guesses.sort_by_key(|guess| (guess.timestamp, guess.player_id));
 
     // Calculate rank bonuses with tie detection
-    let rank_bonuses = calculate_rank_bonuses(&sorted_guesses);
+    let rank_bonuses = calculate_rank_bonuses(&sorted_guesses, config);
 
     // Calculate individual scores
     for (i, guess) in sorted_guesses.iter().enumerate() {
-        let time_score = calculate_time_score(guess.normalized_time);
+        let time_score = calculate_time_score(guess.normalized_time, config);
         let rank_bonus = rank_bonuses[i];
         let total_score = time_score + rank_bonus;
         
@@ -118,16 +154,16 @@ fn calculate_guesser_scores(
 }
 
 /// Calculate time-based score component
-fn calculate_time_score(normalized_time: f64) -> u32 {
+fn calculate_time_score(normalized_time: f64, config: &ScoringConfig) -> u32 {
     let clamped_time = normalized_time.clamp(0.0, 1.0);
-    let time_score = SCORING_CONSTANTS.pmin as f64 + 
-        (SCORING_CONSTANTS.pmax as f64 - SCORING_CONSTANTS.pmin as f64) * clamped_time;
+    let time_score = config.pmin as f64 + 
+        (config.pmax as f64 - config.pmin as f64) * clamped_time;
     
     time_score.floor() as u32
 }
 
 /// Calculate rank bonuses with tie detection
-fn calculate_rank_bonuses(guesses: &[&Guess]) -> Vec<u32> {
+fn calculate_rank_bonuses(guesses: &[&Guess], config: &ScoringConfig) -> Vec<u32> {
     let mut bonuses = vec![0; guesses.len()];
     
     if guesses.is_empty() {
@@ -137,15 +173,16 @@ fn calculate_rank_bonuses(guesses: &[&Guess]) -> Vec<u32> {
     let mut current_bonus_index = 0;
     let mut i = 0;
 
-    while i < guesses.len() && current_bonus_index < SCORING_CONSTANTS.rank_bonuses.len() {
+    while i < guesses.len() && current_bonus_index < config.rank_bonuses.len() {
         let current_time = guesses[i].timestamp.timestamp_millis() as u64;
         
-        // Find all guesses within tie window
+        // Find all guesses within tie window of the group's first guess; guesses are sorted,
+        // so identical timestamps always land in the same group whatever their order
         let mut tie_count = 1;
         let mut j = i + 1;
         while j < guesses.len() {
             let time_diff = (guesses[j].timestamp.timestamp_millis() as u64).saturating_sub(current_time);
-            if time_diff <= SCORING_CONSTANTS.tie_window_ms {
+            if time_diff <= config.tie_window_ms {
                 tie_count += 1;
                 j += 1;
             } else {
@@ -154,9 +191,9 @@ fn calculate_rank_bonuses(guesses: &[&Guess]) -> Vec<u32> {
         }
 
         // Assign same bonus to all tied guesses
-        let bonus = SCORING_CONSTANTS.rank_bonuses[current_bonus_index];
-        for k in i..i + tie_count {
-            bonuses[k] = bonus;
+        let bonus = config.rank_bonuses[current_bonus_index];
+        for slot in bonuses.iter_mut().skip(i).take(tie_count) {
+            *slot = bonus;
         }
 
         // Competition ranking: if two tie for 1st, both get 1st; next rank is 3rd
@@ -173,16 +210,17 @@ fn calculate_artist_score(
     median_guess_time: f64,
     top_guesser_score: u32,
     artist_streak: u32,
+    config: &ScoringConfig,
 ) -> u32 {
     // Base artist score calculation
-    let artist_raw = SCORING_CONSTANTS.base as f64 * fraction_guessed * (0.5 + 0.5 * median_guess_time);
+    let artist_raw = config.base as f64 * fraction_guessed * (0.5 + 0.5 * median_guess_time);
     
     // Add streak bonus
-    let streak_bonus = (SCORING_CONSTANTS.streak_bonus_per_tier * artist_streak.min(SCORING_CONSTANTS.max_streak)) as f64;
+    let streak_bonus = (config.streak_bonus_per_tier * artist_streak.min(config.max_streak)) as f64;
     let artist_with_streak = artist_raw + streak_bonus;
     
     // Cap to keep artist below top guesser
-    let cap = (SCORING_CONSTANTS.cap_ratio * top_guesser_score as f64).floor() as u32;
+    let cap = (config.cap_ratio * top_guesser_score as f64).floor() as u32;
     
     artist_with_streak.round().min(cap as f64) as u32
 }
@@ -192,34 +230,127 @@ pub fn should_increment_artist_streak(
     correct_guesses: &[Guess],
     round_duration: u32,
     potential_guessers: u32,
+    config: &ScoringConfig,
 ) -> bool {
     if correct_guesses.is_empty() {
         return false;
     }
 
     let halfway_point = round_duration / 2;
-    let required_half = (potential_guessers / 2) + 1; // Strictly more than 50%
+    // At least one guess is always needed, even with a threshold of 0
+    let required = ((config.artist_streak_threshold * potential_guessers as f64).ceil() as u32).max(1);
 
     let guesses_by_halfway = correct_guesses
         .iter()
         .filter(|guess| guess.time_remaining >= halfway_point)
         .count();
 
-    guesses_by_halfway >= required_half as usize
+    guesses_by_halfway >= required as usize
 }
 
 /// Update artist streak based on round performance
 pub fn update_artist_streak(
     current_streak: u32,
     should_increment: bool,
+    config: &ScoringConfig,
 ) -> u32 {
     if should_increment {
-        (current_streak + 1).min(SCORING_CONSTANTS.max_streak)
+        (current_streak + 1).min(config.max_streak)
     } else {
         0
     }
 }
 
+/// Work out each guesser's consecutive-guessed-rounds streak after this round, adding the
+/// optional streak bonus to their score. The drawer's streak is left untouched.
+pub fn update_guess_streaks(room: &Room, scores: &mut RoundScores) {
+    let config = &room.scoring;
+    for player in room.players.values() {
+        if room.current_drawer == Some(player.id) {
+            continue;
+        }
+        if let Some(score) = scores.guesser_scores.get_mut(&player.id) {
+            let streak = player.guess_streak + 1;
+            *score += config.guess_streak_bonus * (streak - 1).min(config.max_streak);
+            scores.guess_streaks.insert(player.id, streak);
+        } else {
+            scores.guess_streaks.insert(player.id, 0);
+        }
+    }
+}
+
+/// Scale guesser and artist scores by the room's multiplier for the current cycle, so the
+/// broadcast RoundScores match what players are actually awarded
+pub fn apply_round_multiplier(room: &Room, scores: &mut RoundScores) {
+    let multiplier = room.score_multiplier();
+    if multiplier == 1.0 {
+        return;
+    }
+    let scale = |score: u32| (score as f64 * multiplier).round() as u32;
+    for score in scores.guesser_scores.values_mut() {
+        *score = scale(*score);
+    }
+    scores.artist_score = scale(scores.artist_score);
+}
+
+/// Apply round scores to the room's players and update the artist and guess streaks
+pub fn update_player_scores(room: &mut Room, scores: &RoundScores) {
+    // Update guesser scores
+    for (player_id, score) in &scores.guesser_scores {
+        if let Some(player) = room.players.get_mut(player_id) {
+            player.add_score(*score);
+        }
+    }
+
+    // Update artist score and streak
+    let config = room.scoring.clone();
+    if let Some(drawer_id) = room.current_drawer {
+        let potential_guessers = room.potential_guessers();
+
+        if let Some(player) = room.players.get_mut(&drawer_id) {
+            player.add_score(scores.artist_score);
+
+            let should_increment = should_increment_artist_streak(
+                &scores.correct_guesses,
+                scores.round_duration,
+                potential_guessers as u32,
+                &config,
+            );
+
+            player.artist_streak = update_artist_streak(player.artist_streak, should_increment, &config);
+        }
+    }
+
+    // Update guess streaks
+    for (player_id, streak) in &scores.guess_streaks {
+        if let Some(player) = room.players.get_mut(player_id) {
+            player.guess_streak = *streak;
+        }
+    }
+}
+
+/// Rank players by score, highest first, using competition ranking for ties
+pub fn rank_players<'a>(players: impl IntoIterator<Item = &'a Player>) -> Vec<FinalRanking> {
+    let mut sorted: Vec<&Player> = players.into_iter().collect();
+    // Username as secondary key keeps tied players in a stable order
+    sorted.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.username.cmp(&b.username)));
+
+    let mut rankings: Vec<FinalRanking> = Vec::with_capacity(sorted.len());
+    for (i, player) in sorted.iter().enumerate() {
+        let rank = match rankings.last() {
+            Some(prev) if prev.score == player.score => prev.rank,
+            _ => i as u32 + 1,
+        };
+        rankings.push(FinalRanking {
+            player_id: player.id,
+            username: player.username.clone(),
+            score: player.score,
+            rank,
+        });
+    }
+    rankings
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,22 +359,22 @@ mod tests {
     #[test]
     fn test_time_score_calculation() {
         // Test early guess (high score)
-        let early_score = calculate_time_score(1.0);
+        let early_score = calculate_time_score(1.0, &SCORING_CONSTANTS);
         assert_eq!(early_score, SCORING_CONSTANTS.pmax);
         
         // Test late guess (low score)
-        let late_score = calculate_time_score(0.0);
+        let late_score = calculate_time_score(0.0, &SCORING_CONSTANTS);
         assert_eq!(late_score, SCORING_CONSTANTS.pmin);
         
         // Test middle guess
-        let middle_score = calculate_time_score(0.5);
+        let middle_score = calculate_time_score(0.5, &SCORING_CONSTANTS);
         let expected = SCORING_CONSTANTS.pmin + (SCORING_CONSTANTS.pmax - SCORING_CONSTANTS.pmin) / 2;
         assert_eq!(middle_score, expected);
     }
 
     #[test]
     fn test_rank_bonuses() {
-        let mut guesses = vec![
+        let guesses = [
             Guess {
                 player_id: Uuid::new_v4(),
                 username: "Player1".to_string(),
@@ -256,20 +387,21 @@ mod tests {
                 player_id: Uuid::new_v4(),
                 username: "Player2".to_string(),
                 word: "test".to_string(),
-                timestamp: Utc::now(),
+                timestamp: Utc::now() + chrono::Duration::milliseconds(500),
                 time_remaining: 80,
                 normalized_time: 0.8,
             },
         ];
 
-        let bonuses = calculate_rank_bonuses(guesses.iter().collect());
+        let sorted: Vec<&Guess> = guesses.iter().collect();
+        let bonuses = calculate_rank_bonuses(&sorted, &SCORING_CONSTANTS);
         assert_eq!(bonuses[0], 100); // 1st place
         assert_eq!(bonuses[1], 60);  // 2nd place
     }
 
     #[test]
     fn test_artist_score_calculation() {
-        let score = calculate_artist_score(0.8, 0.6, 500, 2);
+        let score = calculate_artist_score(0.8, 0.6, 500, 2, &SCORING_CONSTANTS);
         assert!(score > 0);
         assert!(score <= 400); // Should be capped at 80% of top guesser
     }
@@ -277,8 +409,7 @@ mod tests {
     #[test]
     fn test_streak_increment_logic() {
         let round_duration = 120;
-        let potential_guessers = 4;
-        let required_half = (potential_guessers / 2) + 1; // 3
+        let potential_guessers = 4; // 2 required
 
         // Test case: 3 out of 4 guessed by halfway (should increment)
         let guesses = vec![
@@ -308,7 +439,177 @@ mod tests {
             },
         ];
 
-        let should_increment = should_increment_artist_streak(&guesses, round_duration, potential_guessers);
+        let should_increment = should_increment_artist_streak(&guesses, round_duration, potential_guessers, &SCORING_CONSTANTS);
         assert!(should_increment);
     }
+
+    #[test]
+    fn test_rankings_share_rank_on_tie_and_skip_next() {
+        let mut players = Vec::new();
+        for (name, score) in [("carol", 300), ("alice", 500), ("bob", 500), ("dave", 100)] {
+            let mut p = Player::new(name);
+            p.score = score;
+            players.push(p);
+        }
+
+        let rankings = rank_players(&players);
+        let summary: Vec<(&str, u32, u32)> = rankings
+            .iter()
+            .map(|r| (r.username.as_str(), r.score, r.rank))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("alice", 500, 1), ("bob", 500, 1), ("carol", 300, 3), ("dave", 100, 4)]
+        );
+    }
+
+    #[test]
+    fn test_final_round_multiplier_applies_only_to_last_cycle() {
+        let state = crate::state::AppState::new();
+        let (room_code, ids) = crate::test_support::room_with_players(&state, &["artist", "guesser"]);
+        crate::test_support::start_round(&state, &room_code, ids[0], "apple");
+        let mut room = state.get_room(&room_code).unwrap();
+        room.final_round_multiplier = 2.0;
+        room.max_rounds = 3;
+
+        let guess = Guess {
+            player_id: ids[1],
+            username: "guesser".to_string(),
+            word: "apple".to_string(),
+            timestamp: Utc::now(),
+            time_remaining: 30,
+            normalized_time: 0.5,
+        };
+        let base = calculate_round_scores(1, "apple", 60, vec![guess], 1, 0, &room.scoring);
+
+        let mut early = base.clone();
+        apply_round_multiplier(&room, &mut early);
+        assert_eq!(early.guesser_scores, base.guesser_scores);
+        assert_eq!(early.artist_score, base.artist_score);
+
+        room.cycle_number = 3;
+        let mut last = base.clone();
+        apply_round_multiplier(&room, &mut last);
+        assert_eq!(last.guesser_scores[&ids[1]], base.guesser_scores[&ids[1]] * 2);
+        assert_eq!(last.artist_score, base.artist_score * 2);
+    }
+
+    #[test]
+    fn test_identical_timestamps_share_first_place_in_any_order() {
+        let at = Utc::now();
+        let guess_by = |player_id: Uuid| Guess {
+            player_id,
+            username: "guesser".to_string(),
+            word: "apple".to_string(),
+            timestamp: at,
+            time_remaining: 30,
+            normalized_time: 0.5,
+        };
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let config = ScoringConfig::default();
+
+        let forward = calculate_guesser_scores(&[guess_by(a), guess_by(b)], 60, 2, &config);
+        let reverse = calculate_guesser_scores(&[guess_by(b), guess_by(a)], 60, 2, &config);
+        let first_place = calculate_time_score(0.5, &config) + config.rank_bonuses[0];
+        assert_eq!(forward[&a], first_place);
+        assert_eq!(forward[&b], first_place);
+        assert_eq!(forward, reverse);
+    }
+
+    #[test]
+    fn test_warmup_first_round_scores_nothing() {
+        let state = crate::state::AppState::new();
+        let (room_code, ids) = crate::test_support::room_with_players(&state, &["artist", "guesser"]);
+        crate::test_support::start_round(&state, &room_code, ids[0], "apple");
+        let mut room = state.get_room(&room_code).unwrap();
+        room.warmup_first_round = true;
+        room.max_rounds = 3;
+
+        let guess = Guess {
+            player_id: ids[1],
+            username: "guesser".to_string(),
+            word: "apple".to_string(),
+            timestamp: Utc::now(),
+            time_remaining: 30,
+            normalized_time: 0.5,
+        };
+        let base = calculate_round_scores(1, "apple", 60, vec![guess], 1, 0, &room.scoring);
+        assert!(base.guesser_scores[&ids[1]] > 0);
+
+        let mut warmup = base.clone();
+        apply_round_multiplier(&room, &mut warmup);
+        update_player_scores(&mut room, &warmup);
+        assert_eq!(room.players[&ids[1]].score, 0);
+        assert_eq!(room.players[&ids[0]].score, 0);
+
+        room.cycle_number = 2;
+        let mut second = base.clone();
+        apply_round_multiplier(&room, &mut second);
+        update_player_scores(&mut room, &second);
+        assert_eq!(room.players[&ids[1]].score, base.guesser_scores[&ids[1]]);
+        assert_eq!(room.players[&ids[0]].score, base.artist_score);
+    }
+
+    #[test]
+    fn test_early_finish_bonus_rewards_fast_full_rounds() {
+        let config = ScoringConfig { early_finish_bonus: 120, ..ScoringConfig::default() };
+        let guess_with = |time_remaining| Guess {
+            player_id: Uuid::nil(),
+            username: "guesser".to_string(),
+            word: "apple".to_string(),
+            timestamp: Utc::now(),
+            time_remaining,
+            normalized_time: 0.5,
+        };
+        let artist_score = |time_remaining, config: &ScoringConfig| {
+            calculate_round_scores(1, "apple", 60, vec![guess_with(time_remaining)], 1, 0, config).artist_score
+        };
+
+        let base = artist_score(50, &SCORING_CONSTANTS);
+        assert_eq!(artist_score(50, &config), base + 100);
+        assert_eq!(artist_score(5, &config), base + 10);
+    }
+
+    #[test]
+    fn test_deducting_more_than_a_player_has_leaves_zero() {
+        let mut player = Player::new("broke");
+        player.add_score(30);
+        player.deduct_score(50);
+        assert_eq!(player.score, 0);
+
+        player.score = u32::MAX - 1;
+        player.add_score(10);
+        assert_eq!(player.score, u32::MAX);
+    }
+
+    #[test]
+    fn test_artist_streak_threshold_is_ceiling_of_fraction() {
+        let guesses_by_halfway = |count: usize| -> Vec<Guess> {
+            (0..count)
+                .map(|_| Guess {
+                    player_id: Uuid::new_v4(),
+                    username: "guesser".to_string(),
+                    word: "apple".to_string(),
+                    timestamp: Utc::now(),
+                    time_remaining: 50,
+                    normalized_time: 0.2,
+                })
+                .collect()
+        };
+        // Fewest early guesses that grow the streak
+        let required = |potential_guessers: u32, config: &ScoringConfig| {
+            (1..=potential_guessers as usize)
+                .find(|&n| should_increment_artist_streak(&guesses_by_halfway(n), 60, potential_guessers, config))
+                .unwrap()
+        };
+
+        assert_eq!(required(3, &SCORING_CONSTANTS), 2);
+        assert_eq!(required(4, &SCORING_CONSTANTS), 2);
+        assert_eq!(required(5, &SCORING_CONSTANTS), 3);
+
+        let strict = ScoringConfig { artist_streak_threshold: 0.75, ..ScoringConfig::default() };
+        assert_eq!(required(3, &strict), 3);
+        assert_eq!(required(4, &strict), 3);
+        assert_eq!(required(5, &strict), 4);
+    }
 }
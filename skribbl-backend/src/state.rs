@@ -1,16 +1,127 @@
-use crate::models::{Room, Player, GameState};
+use crate::config::ServerConfig;
+use crate::models::{Room, Player, PlayerState, GameState, RoomEvent, RoomEventKind, RoundOutcome};
+use std::collections::VecDeque;
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use chrono::Utc;
 use axum::extract::ws::Message;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify};
+use tokio::task::AbortHandle;
+
+// Messages queued per connection before it counts as a slow consumer and is dropped
+pub const SEND_QUEUE_CAPACITY: usize = 512;
+
+// Sending half of a connection's bounded outbound queue. Sends never block: if the queue is
+// full the client has stopped keeping up, so the connection is flagged for eviction instead.
+#[derive(Clone)]
+pub struct ConnectionSender {
+    tx: mpsc::Sender<Message>,
+    evicted: Arc<Notify>,
+    closed: Arc<Notify>,
+}
+
+impl ConnectionSender {
+    pub fn send(&self, message: Message) -> Result<(), mpsc::error::TrySendError<Message>> {
+        let result = self.tx.try_send(message);
+        if let Err(mpsc::error::TrySendError::Full(_)) = &result {
+            self.evicted.notify_one();
+        }
+        result
+    }
+
+    // Resolves once a send has found the queue full
+    pub async fn evicted(&self) {
+        self.evicted.notified().await
+    }
+
+    // Queue a close frame and tell the socket's read loop to stop serving it
+    pub fn close(&self) {
+        let _ = self.tx.try_send(Message::Close(None));
+        self.closed.notify_one();
+    }
+
+    // Resolves once the server has closed the connection
+    pub async fn closed(&self) {
+        self.closed.notified().await
+    }
+
+    // Whether both senders feed the same connection
+    pub fn same_channel(&self, other: &ConnectionSender) -> bool {
+        self.tx.same_channel(&other.tx)
+    }
+}
+
+// Create a connection's outbound queue
+pub fn connection_channel() -> (ConnectionSender, mpsc::Receiver<Message>) {
+    let (tx, rx) = mpsc::channel(SEND_QUEUE_CAPACITY);
+    (ConnectionSender { tx, evicted: Arc::new(Notify::new()), closed: Arc::new(Notify::new()) }, rx)
+}
+
+// Compare a presented secret against the expected one without leaking, through timing,
+// how much of it matched. Hashing first also hides the expected secret's length.
+pub fn secrets_match(expected: &str, given: &str) -> bool {
+    use sha2::{Digest, Sha256};
+    let expected = Sha256::digest(expected.as_bytes());
+    let given = Sha256::digest(given.as_bytes());
+    expected.iter().zip(given.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
 
 // WebSocket connection info
 pub struct WebSocketConnection {
+    pub player_id: Uuid, // For observers, an ID that belongs to no player
+    pub room_code: String,
+    pub sender: ConnectionSender,
+    pub observer: bool, // Admin observer: sees the unfiltered room, can't act in it
+}
+
+// Canvas size used until the host picks another
+pub const DEFAULT_CANVAS_WIDTH: u32 = 800;
+pub const DEFAULT_CANVAS_HEIGHT: u32 = 600;
+
+// Spectator slots a room offers once its player slots are full
+const DEFAULT_MAX_SPECTATORS: u8 = 4;
+
+// Winners-chat messages per player per round until the host changes it
+const DEFAULT_WINNERS_CHAT_CAP: u32 = 20;
+
+// Letters auto-revealed each round until the host changes it
+const DEFAULT_HINT_COUNT: u32 = 2;
+
+// Price and per-round limit for bought hints until the host changes them
+const DEFAULT_HINT_COST: u32 = 50;
+const DEFAULT_MAX_HINTS_PER_ROUND: u32 = 2;
+
+// Finished-round drawings kept per room for the gallery
+const MAX_GALLERY_ENTRIES: usize = 20;
+
+// Most recent debug events kept per room
+const MAX_ROOM_EVENTS: usize = 200;
+
+// One of an IP's WebSocket connection slots; the slot is released when dropped
+pub struct IpConnectionSlot {
+    ip: IpAddr,
+    counts: Arc<DashMap<IpAddr, usize>>,
+}
+
+impl Drop for IpConnectionSlot {
+    fn drop(&mut self) {
+        if let Some(mut count) = self.counts.get_mut(&self.ip) {
+            *count = count.saturating_sub(1);
+        }
+        self.counts.remove_if(&self.ip, |_, count| *count == 0);
+    }
+}
+
+// What a /ws ticket from POST /rooms stands for until it is redeemed or expires
+#[derive(Debug, Clone)]
+pub struct WsTicket {
     pub player_id: Uuid,
     pub room_code: String,
-    pub sender: mpsc::UnboundedSender<Message>,
+    pub expires_at: Instant,
 }
 
 // Global application state for storing rooms and players
@@ -19,16 +130,114 @@ pub struct AppState {
     pub rooms: Arc<DashMap<String, Room>>,      // Room code -> Room
     pub players: Arc<DashMap<Uuid, Player>>,    // Player ID -> Player
     pub connections: Arc<DashMap<Uuid, WebSocketConnection>>, // Player ID -> WebSocket connection
+    pub last_guess_at: Arc<DashMap<Uuid, Instant>>, // Player ID -> last evaluated guess
+    pub auth_tokens: Arc<DashMap<Uuid, String>>, // Player ID -> opaque auth token issued at create/join
+    pub ws_tickets: Arc<DashMap<String, WsTicket>>, // One-shot /ws ticket -> the player it binds to
+    pub connections_per_ip: Arc<DashMap<IpAddr, usize>>, // Client IP -> open WebSocket connections
+    pub rooms_per_creator: Arc<DashMap<String, usize>>, // Lowercased creator username -> active rooms
+    pub room_creators: Arc<DashMap<String, String>>, // Room code -> lowercased creator username
+    pub room_events: Arc<DashMap<String, VecDeque<RoomEvent>>>, // Room code -> recent events (debug only)
+    pub round_timers: Arc<DashMap<String, AbortHandle>>, // Room code -> the one live round timer
+    pub game_clocks: Arc<DashMap<String, AbortHandle>>, // Room code -> the current game's wall-clock limit
+    pub config: Arc<ServerConfig>,
 }
 
 impl AppState {
-    // Create a new AppState instance
+    // Create a new AppState instance, configured from the environment
     pub fn new() -> Self {
+        Self::with_config(ServerConfig::from_env())
+    }
+
+    // Create a new AppState instance with explicit server configuration
+    pub fn with_config(config: ServerConfig) -> Self {
         Self {
             rooms: Arc::new(DashMap::new()),
             players: Arc::new(DashMap::new()),
             connections: Arc::new(DashMap::new()),
+            last_guess_at: Arc::new(DashMap::new()),
+            auth_tokens: Arc::new(DashMap::new()),
+            ws_tickets: Arc::new(DashMap::new()),
+            connections_per_ip: Arc::new(DashMap::new()),
+            rooms_per_creator: Arc::new(DashMap::new()),
+            room_creators: Arc::new(DashMap::new()),
+            room_events: Arc::new(DashMap::new()),
+            round_timers: Arc::new(DashMap::new()),
+            game_clocks: Arc::new(DashMap::new()),
+            config: Arc::new(config),
+        }
+    }
+
+    // Issue a fresh opaque auth token for a player, replacing any previous one
+    pub fn issue_auth_token(&self, player_id: Uuid) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        self.auth_tokens.insert(player_id, token.clone());
+        token
+    }
+
+    // Check that the token was issued to this player
+    pub fn validate_auth_token(&self, player_id: &Uuid, token: &str) -> bool {
+        self.auth_tokens
+            .get(player_id)
+            .map(|issued| !token.is_empty() && secrets_match(&issued, token))
+            .unwrap_or(false)
+    }
+
+    // Issue a short-lived ticket that binds one /ws upgrade straight to this player
+    pub fn issue_ws_ticket(&self, player_id: Uuid, room_code: &str) -> String {
+        let now = Instant::now();
+        self.ws_tickets.retain(|_, ticket| ticket.expires_at > now);
+        let ticket = Uuid::new_v4().simple().to_string();
+        self.ws_tickets.insert(ticket.clone(), WsTicket {
+            player_id,
+            room_code: room_code.to_string(),
+            expires_at: now + Duration::from_secs(self.config.ws_ticket_secs),
+        });
+        ticket
+    }
+
+    // Consume a ticket; it is gone afterwards whether or not it was still valid. Outstanding
+    // tickets are scanned with secrets_match rather than looked up, so the match isn't timeable.
+    pub fn redeem_ws_ticket(&self, ticket: &str) -> Option<WsTicket> {
+        let issued = self
+            .ws_tickets
+            .iter()
+            .find(|entry| secrets_match(entry.key(), ticket))
+            .map(|entry| entry.key().clone())?;
+        self.ws_tickets
+            .remove(&issued)
+            .map(|(_, ticket)| ticket)
+            .filter(|ticket| ticket.expires_at > Instant::now())
+    }
+
+    // Reserve a connection slot for an IP, or None if it already has the configured maximum open
+    pub fn try_acquire_ip_slot(&self, ip: IpAddr) -> Option<IpConnectionSlot> {
+        let mut count = self.connections_per_ip.entry(ip).or_insert(0);
+        if *count >= self.config.max_connections_per_ip {
+            return None;
         }
+        *count += 1;
+        Some(IpConnectionSlot {
+            ip,
+            counts: self.connections_per_ip.clone(),
+        })
+    }
+
+    // Record a guess attempt; returns false if the player is still inside the guess cooldown
+    pub fn try_start_guess(&self, player_id: Uuid) -> bool {
+        let cooldown = Duration::from_millis(self.config.guess_cooldown_ms);
+        let now = Instant::now();
+        let mut allowed = true;
+        self.last_guess_at
+            .entry(player_id)
+            .and_modify(|last| {
+                if now.duration_since(*last) < cooldown {
+                    allowed = false;
+                } else {
+                    *last = now;
+                }
+            })
+            .or_insert(now);
+        allowed
     }
 
     // Generate a unique 6-character room code
@@ -52,33 +261,122 @@ impl AppState {
         }
     }
 
-    // Create a new room
-    pub fn create_room(&self, room_code: String, round_duration: u32, max_players: u8, host_id: Uuid) -> Room {
+    // Create a new room; fails if the code is already in use
+    pub fn create_room(&self, room_code: String, round_duration: u32, max_players: u8, host_id: Uuid) -> Result<Room, String> {
         let room = Room {
             id: Uuid::new_v4(),
             code: room_code.clone(),
             host_id,
             players: std::collections::HashMap::new(),
             current_drawer: None,
+            draw_order: Vec::new(),
             word: None,
+            pending_word_choices: Vec::new(),
+            word_choices_rerolled: false,
+            custom_words: Vec::new(),
+            min_word_length: crate::words::DEFAULT_MIN_WORD_LENGTH,
             round_number: 0,
             max_rounds: 3, // Default to 3 rounds
             cycle_number: 1, // Start at cycle 1, not 0
+            rounds_remaining: 0,
+            rounds_played: 0,
+            used_words: Vec::new(),
             round_duration,
+            guess_delay_secs: 0,
+            show_pattern_after: 0.0,
+            guess_matching: crate::models::GuessMatching::Strict,
+            hint_count: DEFAULT_HINT_COUNT,
+            start_reveal: 0,
+            final_round_multiplier: 1.0,
+            warmup_first_round: false,
+            sudden_death: false,
+            sudden_death_players: Vec::new(),
+            require_ready: false,
+            play_again_votes: Vec::new(),
+            max_game_duration_secs: None,
+            game_started_at: None,
+            practice: false,
+            persistent: false,
             game_state: GameState::Waiting,
             round_start_time: None,
             round_end_time: None,
+            paused_remaining_ms: None,
             drawing_paths: Vec::new(),
+            clear_canvas_on: crate::models::ClearTiming::RoundEnd,
+            max_undo: None,
+            canvas_width: DEFAULT_CANVAS_WIDTH,
+            canvas_height: DEFAULT_CANVAS_HEIGHT,
+            undos_used: 0,
+            min_draw_time_before_skip: 0,
+            skip_votes: Vec::new(),
+            hint_cost: DEFAULT_HINT_COST,
+            max_hints_per_round: DEFAULT_MAX_HINTS_PER_ROUND,
+            hint_purchases: std::collections::HashMap::new(),
+            gallery: Vec::new(),
             chat_messages: Vec::new(),
             current_round_guesses: Vec::new(),
             winners: Vec::new(),
+            scoring: crate::scoring::ScoringConfig::default(),
+            round_token: 0,
             max_players,
+            allow_spectators: true,
+            max_spectators: DEFAULT_MAX_SPECTATORS,
+            allow_spectator_chat: true,
+            winners_chat_cap: DEFAULT_WINNERS_CHAT_CAP,
+            winners_chat_sent: std::collections::HashMap::new(),
+            near_miss_streaks: std::collections::HashMap::new(),
+            muted: std::collections::HashSet::new(),
+            reveal_drawer: true,
+            show_draw_order: true,
+            drawer_sees_guesses: true,
+            verifiable_words: false,
+            word_seed: None,
+            word_draws: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
         
-        self.rooms.insert(room_code, room.clone());
-        room
+        // Insert only into a vacant slot so two concurrent creates can't overwrite each other
+        match self.rooms.entry(room_code) {
+            Entry::Vacant(entry) => {
+                entry.insert(room.clone());
+                Ok(room)
+            }
+            Entry::Occupied(_) => Err("Room code already in use".to_string()),
+        }
+    }
+
+    // Create a room under a fresh code with `host` as its first player. The host is added via
+    // add_player_to_room like any joiner; the room is discarded if that fails.
+    pub fn create_room_with_host(&self, round_duration: u32, max_players: u8, host: Player) -> Result<Room, String> {
+        if self.at_room_capacity() {
+            return Err("Server is at room capacity, try again later".to_string());
+        }
+
+        // Reserve a slot against the creator's cap before the room exists
+        let creator = host.username.to_lowercase();
+        {
+            let max_rooms = self.config.max_rooms_per_creator;
+            let mut count = self.rooms_per_creator.entry(creator.clone()).or_insert(0);
+            if *count >= max_rooms {
+                return Err(format!("You already have {} active rooms", max_rooms));
+            }
+            *count += 1;
+        }
+
+        let room_code = loop {
+            let code = self.generate_room_code();
+            if self.create_room(code.clone(), round_duration, max_players, host.id).is_ok() {
+                break code;
+            }
+        };
+        self.room_creators.insert(room_code.clone(), creator);
+
+        if let Err(e) = self.add_player_to_room(&room_code, host) {
+            self.remove_room(&room_code);
+            return Err(e);
+        }
+        self.get_room(&room_code).ok_or_else(|| "Room not found".to_string())
     }
 
     // Get a room by its code
@@ -86,27 +384,53 @@ impl AppState {
         self.rooms.get(room_code).map(|room| room.clone())
     }
 
-    // Add a player to a room
-    pub fn add_player_to_room(&self, room_code: &str, player: Player) -> Result<(), String> {
+    // Add a player to a room, returning the player as admitted
+    // Finished rooms are closed to new players; the host has to start a new room instead.
+    // Once the player slots are full, joiners overflow into spectator slots if the room allows it.
+    pub fn add_player_to_room(&self, room_code: &str, mut player: Player) -> Result<Player, String> {
         if let Some(mut room) = self.rooms.get_mut(room_code) {
+            if room.game_state == GameState::Finished {
+                return Err("Game has already finished".to_string());
+            }
+
             // Check if room is full
-            if room.players.len() >= room.max_players as usize {
+            if room.is_full() {
                 return Err("Room is full".to_string());
             }
+            if room.active_player_count() >= room.max_players as usize {
+                player.state = PlayerState::Spectator;
+            }
             
             // Check if username is already taken in this room
             if room.players.values().any(|p| p.username == player.username) {
                 return Err("Username already taken in this room".to_string());
             }
             
+            // Mid-game joiners take the last slot in the rotation
+            if room.game_state == GameState::Playing
+                && player.state != PlayerState::Spectator
+                && !room.draw_order.is_empty()
+                && !room.draw_order.contains(&player.id) {
+                room.draw_order.push(player.id);
+            }
+
+            // Whoever walks into an emptied persistent lobby runs it
+            if room.players.is_empty() {
+                room.host_id = player.id;
+            }
+
             // Add player to room
             room.players.insert(player.id, player.clone());
             room.updated_at = Utc::now();
+            self.record_event(room_code, RoomEventKind::PlayerJoined {
+                player_id: player.id,
+                username: player.username.clone(),
+            });
             
             // Also store player in global players map
-            self.players.insert(player.id, player);
+            self.players.insert(player.id, player.clone());
             
-            Ok(())
+            Ok(player)
         } else {
             Err("Room not found".to_string())
         }
@@ -124,6 +448,10 @@ impl AppState {
                 
                 if let Some(player) = room.players.remove(player_id) {
                     println!("Player found and removed from room");
+                    // A leaving drawer keeps their slot until the round ends so the rotation knows where it was
+                    if room.current_drawer != Some(*player_id) {
+                        room.draw_order.retain(|id| id != player_id);
+                    }
                     room.updated_at = Utc::now();
                     
                     // Check if room will be empty after this player leaves
@@ -141,18 +469,38 @@ impl AppState {
             }
         };
         
+        self.record_event(room_code, RoomEventKind::PlayerLeft { player_id: *player_id });
+
         // Now remove from global players map
         self.players.remove(player_id);
+        self.last_guess_at.remove(player_id);
+        self.auth_tokens.remove(player_id);
         println!("Player removed from global players map");
         
+        // A persistent lobby stays up, back in Waiting for whoever joins next
+        let kept = room_will_be_empty
+            && self.with_room_mut(room_code, |room| {
+                if room.persistent {
+                    Self::reset_empty_lobby(room);
+                }
+                room.persistent
+            }) == Some(true);
+        if kept {
+            println!("Keeping empty persistent room {}", room_code);
+            self.abort_room_timers(room_code);
+            // Nobody is running the lobby any more, so it no longer counts against its creator
+            self.release_creator_slot(room_code);
+            self.connections.retain(|_, conn| conn.room_code != room_code);
+        }
+
         // If room is empty, remove it (after releasing the mutable reference)
-        if room_will_be_empty {
+        if room_will_be_empty && !kept {
             println!("Removing empty room");
             // Drop the mutable reference first, then remove
             drop(self.rooms.get_mut(room_code));
             
             // Now it's safe to remove
-            self.rooms.remove(room_code);
+            self.remove_room(room_code);
             println!("Room removed successfully");
             
             // Clean up any remaining connections for this room
@@ -166,12 +514,127 @@ impl AppState {
         result
     }
 
+    // Put an emptied persistent room back to a fresh lobby; its settings are kept
+    fn reset_empty_lobby(room: &mut Room) {
+        room.game_state = GameState::Waiting;
+        room.round_token = room.round_token.wrapping_add(1); // Invalidate timers from the abandoned game
+        room.current_drawer = None;
+        room.draw_order.clear();
+        room.word = None;
+        room.pending_word_choices.clear();
+        room.round_start_time = None;
+        room.round_end_time = None;
+        room.paused_remaining_ms = None;
+        room.game_started_at = None;
+        room.round_number = 0;
+        room.cycle_number = 1;
+        room.rounds_remaining = 0;
+        room.winners.clear();
+        room.current_round_guesses.clear();
+        room.drawing_paths.clear();
+        room.skip_votes.clear();
+        room.hint_purchases.clear();
+        room.winners_chat_sent.clear();
+        room.near_miss_streaks.clear();
+        room.sudden_death_players.clear();
+        room.play_again_votes.clear();
+        room.muted.clear();
+        room.word_seed = None;
+    }
+
+    // Whether the server has reached its global room cap
+    pub fn at_room_capacity(&self) -> bool {
+        self.rooms.len() >= self.config.max_rooms
+    }
+
+    // Delete a room, stop its timers and give its creator's slot back
+    pub fn remove_room(&self, room_code: &str) -> Option<Room> {
+        let room = self.rooms.remove(room_code).map(|(_, room)| room);
+        self.abort_room_timers(room_code);
+        self.room_events.remove(room_code);
+        self.release_creator_slot(room_code);
+        room
+    }
+
+    // Give the room's slot back to the username that created it; a no-op once released
+    fn release_creator_slot(&self, room_code: &str) {
+        if let Some((_, creator)) = self.room_creators.remove(room_code) {
+            if let Some(mut count) = self.rooms_per_creator.get_mut(&creator) {
+                *count = count.saturating_sub(1);
+            }
+            self.rooms_per_creator.remove_if(&creator, |_, count| *count == 0);
+        }
+    }
+
+    // Close a room for good: drop it along with its players' server-side state and connections
+    pub fn close_room(&self, room_code: &str) {
+        if let Some(room) = self.remove_room(room_code) {
+            for player_id in room.players.keys() {
+                self.players.remove(player_id);
+                self.last_guess_at.remove(player_id);
+                self.auth_tokens.remove(player_id);
+            }
+        }
+        self.connections.retain(|_, conn| conn.room_code != room_code);
+    }
+
+    // Make this the room's round timer, aborting any timer that was already running
+    pub fn replace_round_timer(&self, room_code: &str, timer: AbortHandle) {
+        if let Some(previous) = self.round_timers.insert(room_code.to_string(), timer) {
+            previous.abort();
+        }
+    }
+
+    // Make this the room's game clock, aborting the one left from an earlier game
+    pub fn replace_game_clock(&self, room_code: &str, clock: AbortHandle) {
+        if let Some(previous) = self.game_clocks.insert(room_code.to_string(), clock) {
+            previous.abort();
+        }
+    }
+
+    // Stop the room's game clock, if one is running
+    pub fn stop_game_clock(&self, room_code: &str) {
+        if let Some((_, clock)) = self.game_clocks.remove(room_code) {
+            clock.abort();
+        }
+    }
+
+    // Stop everything scheduled for the room: its round timer and its game clock
+    fn abort_room_timers(&self, room_code: &str) {
+        if let Some((_, timer)) = self.round_timers.remove(room_code) {
+            timer.abort();
+        }
+        self.stop_game_clock(room_code);
+    }
+
+    // Append to the room's debug event log, dropping the oldest entry when full; no-op unless debug endpoints are on
+    pub fn record_event(&self, room_code: &str, event: RoomEventKind) {
+        if !self.config.debug_endpoints {
+            return;
+        }
+        let mut events = self.room_events.entry(room_code.to_string()).or_default();
+        if events.len() >= MAX_ROOM_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(RoomEvent { at: Utc::now(), event });
+    }
+
+    // Snapshot of a room's debug event log, oldest first
+    pub fn room_events(&self, room_code: &str) -> Vec<RoomEvent> {
+        self.room_events
+            .get(room_code)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     // Get a player by ID
     pub fn get_player(&self, player_id: &Uuid) -> Option<Player> {
         self.players.get(player_id).map(|player| player.clone())
     }
 
-    // Update an entire room
+    // Update an entire room. Handlers all go through with_room_mut now; tests still use this
+    // to set up fixtures and to show the lost-update race it allows.
+    #[cfg(test)]
     pub fn update_room(&self, room_code: &str, updated_room: Room) -> Result<(), String> {
         if let Some(mut room) = self.rooms.get_mut(room_code) {
             *room = updated_room;
@@ -182,22 +645,265 @@ impl AppState {
         }
     }
 
+    // Mutate a room in place while holding its entry lock. Prefer this over get_room + update_room,
+    // which can silently drop a concurrent handler's changes. The closure must not call back into
+    // AppState methods that touch the same room, or it will deadlock.
+    pub fn with_room_mut<F, R>(&self, room_code: &str, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Room) -> R,
+    {
+        let mut room = self.rooms.get_mut(room_code)?;
+        let result = f(&mut room);
+        room.updated_at = Utc::now();
+        Some(result)
+    }
+
+    // End the current round in a single locked read-modify-write: score it, rotate the drawer,
+    // reset per-round state and detect game end. Returns None if no round is in progress.
+    pub fn end_round(&self, room_code: &str) -> Option<RoundOutcome> {
+        let mut room = self.rooms.get_mut(room_code)?;
+        if room.game_state != GameState::Playing {
+            return None;
+        }
+        let outcome = Self::finish_round(&mut room);
+        room.updated_at = Utc::now();
+        Some(outcome)
+    }
+
+    // Same as end_round, but only if the round identified by `round_token` is still the current one.
+    // Used by the backend timer and the all-guessed path so they can't both end the same round.
+    pub fn end_round_if_current(&self, room_code: &str, round_token: u64) -> Option<RoundOutcome> {
+        let mut room = self.rooms.get_mut(room_code)?;
+        if room.game_state != GameState::Playing || room.round_token != round_token {
+            return None;
+        }
+        let outcome = Self::finish_round(&mut room);
+        room.updated_at = Utc::now();
+        Some(outcome)
+    }
+
+    // End the game early once its wall-clock limit has passed, scoring the round in progress
+    pub fn end_round_if_game_expired(&self, room_code: &str) -> Option<RoundOutcome> {
+        let mut room = self.rooms.get_mut(room_code)?;
+        if room.game_state != GameState::Playing || !room.game_time_exceeded(Utc::now()) {
+            return None;
+        }
+        let outcome = Self::finish_round(&mut room);
+        room.updated_at = Utc::now();
+        Some(outcome)
+    }
+
+    fn finish_round(room: &mut Room) -> RoundOutcome {
+        let potential_guessers = room.potential_guessers();
+        let artist_streak = room
+            .current_drawer
+            .and_then(|d| room.players.get(&d))
+            .map(|p| p.artist_streak)
+            .unwrap_or(0);
+
+        let mut scores = crate::scoring::calculate_round_scores(
+            room.round_number,
+            &room.word.clone().unwrap_or_default(),
+            room.round_duration,
+            room.current_round_guesses.clone(),
+            potential_guessers as u32,
+            artist_streak,
+            &room.scoring,
+        );
+        let mut score_changes = Vec::new();
+        let was_sudden_death = !room.sudden_death_players.is_empty();
+        if was_sudden_death {
+            // Only the first correct guess counts, worth a single tiebreak point
+            scores.guesser_scores.clear();
+            scores.artist_score = 0;
+            let first = room.current_round_guesses.iter().min_by_key(|g| g.timestamp).map(|g| g.player_id);
+            if let Some(player) = first.and_then(|id| room.players.get_mut(&id)) {
+                player.add_score(1);
+                scores.guesser_scores.insert(player.id, 1);
+                score_changes.push(crate::models::ScoreChange { player_id: player.id, new_score: player.score, delta: 1 });
+            }
+        } else if !room.practice {
+            // Practice rounds have nobody to score against
+            crate::scoring::update_guess_streaks(room, &mut scores);
+            crate::scoring::apply_round_multiplier(room, &mut scores);
+            crate::scoring::update_player_scores(room, &scores);
+
+            let artist = room.current_drawer.map(|id| (id, scores.artist_score));
+            for (player_id, delta) in scores.guesser_scores.iter().map(|(id, d)| (*id, *d)).chain(artist) {
+                if let Some(player) = room.players.get(&player_id) {
+                    score_changes.push(crate::models::ScoreChange { player_id, new_score: player.score, delta });
+                }
+            }
+        }
+
+        room.rounds_played = room.rounds_played.saturating_add(1);
+        if let Some(word) = &room.word {
+            room.used_words.push(word.clone());
+        }
+
+        // Keep the finished drawing for the gallery before clearing the canvas
+        if let (Some(word), Some(drawer_id)) = (room.word.clone(), room.current_drawer)
+            && !room.drawing_paths.is_empty() {
+            room.gallery.push(crate::models::GalleryEntry {
+                round_number: room.round_number,
+                cycle_number: room.cycle_number,
+                word,
+                drawer_id,
+                paths: room.drawing_paths.clone(),
+                finished_at: Utc::now(),
+            });
+            if room.gallery.len() > MAX_GALLERY_ENTRIES {
+                room.gallery.remove(0);
+            }
+        }
+
+        // Rotate strictly through the draw order, skipping anyone who has since left or is disconnected
+        if room.draw_order.is_empty() {
+            room.draw_order = room.players_by_join_time();
+        }
+        let order_len = room.draw_order.len();
+        let current_idx = room
+            .current_drawer
+            .and_then(|cur| room.draw_order.iter().position(|id| *id == cur));
+        let start = current_idx.map(|idx| idx + 1).unwrap_or(0);
+        let next_idx = (0..order_len)
+            .map(|offset| (start + offset) % order_len)
+            .find(|&idx| room.players.get(&room.draw_order[idx]).is_some_and(|p| p.is_connected));
+        let mut next_drawer = next_idx.map(|idx| room.draw_order[idx]);
+
+        // Wrapping back past the current drawer starts a new cycle
+        let is_new_cycle = matches!((current_idx, next_idx), (Some(cur), Some(next)) if next <= cur);
+        if is_new_cycle {
+            room.cycle_number = room.cycle_number.saturating_add(1);
+            room.round_number = 1;
+        } else {
+            room.round_number = room.round_number.saturating_add(1);
+        }
+
+        // Drop players who left during the round now that the next drawer is known
+        let players = &room.players;
+        room.draw_order.retain(|id| players.contains_key(id));
+
+        // Check if game should end (max cycles reached); practice rooms loop until left
+        let out_of_time = room.game_time_exceeded(Utc::now());
+        let mut game_over = next_drawer.is_none()
+            || (!room.practice && room.cycle_number > room.max_rounds)
+            || out_of_time;
+        if was_sudden_death {
+            // Sudden death is always the last round, whether or not anyone got it
+            room.sudden_death_players.clear();
+            game_over = true;
+        } else if game_over && !out_of_time && next_drawer.is_some() && room.sudden_death && !room.practice {
+            let tied = room.tied_leaders();
+            if !tied.is_empty() {
+                // Someone outside the tie draws when possible so every contender can guess
+                next_drawer = room
+                    .draw_order
+                    .iter()
+                    .copied()
+                    .find(|id| !tied.contains(id) && room.players.get(id).is_some_and(|p| p.is_connected))
+                    .or(next_drawer);
+                println!("Room {} ended tied between {:?}; starting sudden death", room.code, tied);
+                room.sudden_death_players = tied;
+                game_over = false;
+            }
+        }
+
+        println!(
+            "Round complete in room {}. Next drawer: {:?}, Round: {}, Cycle: {} of {}",
+            room.code, next_drawer, room.round_number, room.cycle_number, room.max_rounds
+        );
+
+        // Reset per-round state
+        room.round_token = room.round_token.wrapping_add(1);
+        room.current_drawer = next_drawer;
+        room.word = None;
+        room.round_start_time = None;
+        room.round_end_time = None;
+        room.paused_remaining_ms = None;
+        room.current_round_guesses.clear();
+        if room.clear_canvas_on == crate::models::ClearTiming::RoundEnd {
+            room.drawing_paths.clear();
+        }
+        room.undos_used = 0;
+        room.skip_votes.clear();
+        room.hint_purchases.clear();
+        room.winners_chat_sent.clear();
+        room.near_miss_streaks.clear();
+        room.word_choices_rerolled = false;
+        room.winners.clear();
+        if let Some(next) = next_drawer {
+            room.winners.push(next); // artist is always a winner
+        }
+        room.pending_word_choices = match next_drawer {
+            Some(_) => crate::words::pick_word_choices_for(room, &[]),
+            None => Vec::new(),
+        };
+
+        room.rounds_remaining = if game_over { 0 } else { room.rounds_after_current() };
+        if game_over {
+            println!("Game ending in room {}: Cycle {} > Max Cycles {}", room.code, room.cycle_number, room.max_rounds);
+            room.game_state = GameState::Finished;
+            for player in room.players.values_mut() {
+                player.ready = false;
+            }
+        }
+
+        RoundOutcome {
+            scores,
+            next_drawer: if game_over { None } else { next_drawer.and_then(|id| room.players.get(&id).cloned()) },
+            game_over,
+            final_scores: room.players.iter().map(|(id, p)| (id.to_string(), p.score)).collect(),
+            rankings: crate::scoring::rank_players(room.players.values()),
+            word_choices: if game_over { Vec::new() } else { room.pending_word_choices.clone() },
+            score_changes,
+            sudden_death_players: room.sudden_death_players.clone(),
+        }
+    }
+
     // Add a WebSocket connection for a player
-    pub fn add_connection(&self, player_id: Uuid, room_code: String, sender: mpsc::UnboundedSender<Message>) {
+    pub fn add_connection(&self, player_id: Uuid, room_code: String, sender: ConnectionSender) {
         let connection = WebSocketConnection {
             player_id,
             room_code,
             sender,
+            observer: false,
         };
         self.connections.insert(player_id, connection);
     }
 
+    // Attach a read-only admin observer to a room, returning the ID its connection is stored under
+    pub fn add_observer(&self, room_code: String, sender: ConnectionSender) -> Uuid {
+        let observer_id = Uuid::new_v4();
+        let connection = WebSocketConnection {
+            player_id: observer_id,
+            room_code,
+            sender,
+            observer: true,
+        };
+        self.connections.insert(observer_id, connection);
+        observer_id
+    }
+
     // Remove a WebSocket connection
     pub fn remove_connection(&self, player_id: &Uuid) {
         self.connections.remove(player_id);
     }
 
+    // Whether this sender is still the player's registered connection, rather than one
+    // that has since been replaced
+    pub fn owns_connection(&self, player_id: &Uuid, sender: &ConnectionSender) -> bool {
+        self.connections.get(player_id).is_some_and(|conn| conn.sender.same_channel(sender))
+    }
+
+
 
+    // Send a message to a single player's connection, if they have one
+    pub fn send_to_player(&self, player_id: &Uuid, message: Message) {
+        if let Some(connection) = self.connections.get(player_id) {
+            let _ = connection.sender.send(message);
+        }
+    }
 
     // Broadcast message to all players in a room
     pub fn broadcast_to_room(&self, room_code: &str, message: Message) {
@@ -229,7 +935,10 @@ impl AppState {
     // Transfer host ownership to the next available player
     pub fn transfer_host_ownership(&self, room_code: &str) -> Result<Uuid, String> {
         if let Some(mut room) = self.rooms.get_mut(room_code) {
-            if let Some(next_host) = room.players.keys().next().cloned() {
+            // Earliest joiner other than the current host, preferring anyone still connected
+            let mut candidates: Vec<&Player> = room.players.values().filter(|p| p.id != room.host_id).collect();
+            candidates.sort_by_key(|p| (!p.is_connected, p.joined_at));
+            if let Some(next_host) = candidates.first().map(|p| p.id) {
                 room.host_id = next_host;
                 room.updated_at = Utc::now();
                 println!("Host ownership transferred to player {}", next_host);
@@ -252,10 +961,9 @@ impl AppState {
     pub fn broadcast_to_winners(&self, room_code: &str, message: Message) {
         if let Some(room) = self.get_room(room_code) {
             for connection in self.connections.iter() {
-                if connection.room_code == room_code {
-                    if Self::is_player_winner(&room, &connection.player_id) {
-                        let _ = connection.sender.send(message.clone());
-                    }
+                if connection.room_code == room_code
+                    && (connection.observer || Self::is_player_winner(&room, &connection.player_id)) {
+                    let _ = connection.sender.send(message.clone());
                 }
             }
         }
@@ -265,34 +973,58 @@ impl AppState {
     pub fn broadcast_to_non_winners(&self, room_code: &str, message: Message) {
         if let Some(room) = self.get_room(room_code) {
             for connection in self.connections.iter() {
-                if connection.room_code == room_code {
-                    if !Self::is_player_winner(&room, &connection.player_id) {
-                        let _ = connection.sender.send(message.clone());
-                    }
+                if connection.room_code == room_code
+                    && !connection.observer
+                    && !Self::is_player_winner(&room, &connection.player_id) {
+                    let _ = connection.sender.send(message.clone());
                 }
             }
         }
     }
 
     // Broadcast GameStateUpdate with server-side filtering per recipient
+    // The room as one player is allowed to see it
+    pub fn room_view_for(room: &Room, player_id: &Uuid) -> Room {
+        let mut visible_room = room.clone();
+        if !Self::is_player_winner(room, player_id) {
+            // Hide the word and winners-only chat from non-winners
+            visible_room.word = None;
+            visible_room.chat_messages.retain(|m| !m.is_winners_only);
+            if !room.reveal_drawer {
+                // Blind artist: the drawer's state would give them away too
+                visible_room.current_drawer = None;
+                for player in visible_room.players.values_mut() {
+                    if player.state == PlayerState::Drawing {
+                        player.state = PlayerState::Guessing;
+                    }
+                }
+            }
+        }
+        if let Some(round_start) = Self::guesses_hidden_from(room, player_id) {
+            visible_room.chat_messages.retain(|m| m.is_winners_only || m.timestamp < round_start);
+        }
+        visible_room
+    }
+
+    // When the room hides guesses from the drawer and this player is drawing a word,
+    // the round start: public chat from then on isn't theirs to see
+    pub fn guesses_hidden_from(room: &Room, player_id: &Uuid) -> Option<chrono::DateTime<Utc>> {
+        if room.drawer_sees_guesses || room.current_drawer != Some(*player_id) || room.word.is_none() {
+            return None;
+        }
+        room.round_start_time
+    }
+
     pub fn broadcast_room_state_filtered(&self, room_code: &str) {
         if let Some(room) = self.get_room(room_code) {
             for connection in self.connections.iter() {
                 if connection.room_code != room_code { continue; }
 
-                let is_winner = Self::is_player_winner(&room, &connection.player_id);
-                let mut visible_room = room.clone();
-
-                if !is_winner {
-                    // Hide the word and winners-only chat from non-winners
-                    visible_room.word = None;
-                    visible_room.chat_messages = visible_room
-                        .chat_messages
-                        .into_iter()
-                        .filter(|m| !m.is_winners_only)
-                        .collect();
-                }
-
+                let visible_room = if connection.observer {
+                    room.clone()
+                } else {
+                    Self::room_view_for(&room, &connection.player_id)
+                };
                 let state_update_msg = crate::models::ServerMessage::GameStateUpdate { room: visible_room };
                 if let Ok(json) = serde_json::to_string(&state_update_msg) {
                     let _ = connection.sender.send(Message::Text(json));
@@ -301,3 +1033,388 @@ impl AppState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{room_with_players, start_round};
+
+    fn guess_for(player_id: Uuid) -> crate::models::Guess {
+        crate::models::Guess {
+            player_id,
+            username: "guesser".to_string(),
+            word: "apple".to_string(),
+            timestamp: Utc::now(),
+            time_remaining: 30,
+            normalized_time: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_secrets_match_only_on_exact_equality() {
+        assert!(secrets_match("hunter2", "hunter2"));
+        assert!(!secrets_match("hunter2", "hunter3"));
+        assert!(!secrets_match("hunter2", "hunter"));
+        assert!(!secrets_match("hunter2", ""));
+    }
+
+    #[test]
+    fn test_clone_and_update_loses_concurrent_guess() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c"]);
+
+        // Two handlers both clone the room before either writes back
+        let mut first = state.get_room(&room_code).unwrap();
+        let mut second = state.get_room(&room_code).unwrap();
+        first.current_round_guesses.push(guess_for(ids[1]));
+        second.current_round_guesses.push(guess_for(ids[2]));
+        state.update_room(&room_code, first).unwrap();
+        state.update_room(&room_code, second).unwrap();
+
+        assert_eq!(state.get_room(&room_code).unwrap().current_round_guesses.len(), 1);
+    }
+
+    #[test]
+    fn test_with_room_mut_keeps_concurrent_guesses() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c"]);
+
+        let handles: Vec<_> = ids[1..]
+            .iter()
+            .map(|&id| {
+                let state = state.clone();
+                let room_code = room_code.clone();
+                std::thread::spawn(move || {
+                    state.with_room_mut(&room_code, |room| room.current_round_guesses.push(guess_for(id)));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(state.get_room(&room_code).unwrap().current_round_guesses.len(), 2);
+    }
+
+    #[test]
+    fn test_connection_beyond_per_ip_limit_is_refused() {
+        let state = AppState::with_config(ServerConfig { max_connections_per_ip: 2, ..ServerConfig::default() });
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let other_ip: IpAddr = "203.0.113.8".parse().unwrap();
+
+        let first = state.try_acquire_ip_slot(ip);
+        let second = state.try_acquire_ip_slot(ip);
+        assert!(first.is_some() && second.is_some());
+        assert!(state.try_acquire_ip_slot(ip).is_none());
+        assert!(state.try_acquire_ip_slot(other_ip).is_some());
+
+        // Closing a connection frees its slot
+        drop(first);
+        assert!(state.try_acquire_ip_slot(ip).is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_simultaneous_correct_guesses_both_recorded() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c", "d"]);
+        start_round(&state, &room_code, ids[0], "apple");
+
+        let handles: Vec<_> = ids[1..3]
+            .iter()
+            .map(|&id| {
+                let state = state.clone();
+                let room_code = room_code.clone();
+                tokio::spawn(async move {
+                    let (tx, _rx) = connection_channel();
+                    crate::websocket::chat::handle_chat(&state, &room_code, "apple", id, "guesser", &tx).await;
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.current_round_guesses.len(), 2);
+        assert!(room.winners.contains(&ids[1]) && room.winners.contains(&ids[2]));
+    }
+
+    #[test]
+    fn test_guess_streak_counts_consecutive_rounds_and_resets_on_miss() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c", "d", "e"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let guesser = ids[4];
+
+        for expected in 1..=3 {
+            state.with_room_mut(&room_code, |room| room.current_round_guesses.push(guess_for(guesser)));
+            let outcome = state.end_round(&room_code).unwrap();
+            assert_eq!(outcome.scores.guess_streaks.get(&guesser), Some(&expected));
+        }
+        assert_eq!(state.get_room(&room_code).unwrap().players[&guesser].guess_streak, 3);
+
+        // A round without a correct guess breaks the streak
+        let outcome = state.end_round(&room_code).unwrap();
+        assert_eq!(outcome.scores.guess_streaks.get(&guesser), Some(&0));
+        assert_eq!(state.get_room(&room_code).unwrap().players[&guesser].guess_streak, 0);
+    }
+
+    #[test]
+    fn test_rooms_per_creator_cap_and_release() {
+        let state = AppState::with_config(ServerConfig { max_rooms_per_creator: 2, ..ServerConfig::default() });
+        let first = state.create_room_with_host(60, 8, Player::new("alice")).unwrap();
+        state.create_room_with_host(60, 8, Player::new("Alice")).unwrap();
+
+        assert!(state.create_room_with_host(60, 8, Player::new("alice")).is_err());
+        assert!(state.create_room_with_host(60, 8, Player::new("bob")).is_ok());
+
+        // The last player leaving deletes the room, which frees the creator's slot
+        state.remove_player_from_room(&first.code, &first.host_id).unwrap();
+        assert!(state.create_room_with_host(60, 8, Player::new("alice")).is_ok());
+    }
+
+    #[test]
+    fn test_emptied_persistent_room_frees_creator_slot() {
+        let state = AppState::with_config(ServerConfig { max_rooms_per_creator: 1, ..ServerConfig::default() });
+        let lobby = state.create_room_with_host(60, 8, Player::new("alice")).unwrap();
+        state.with_room_mut(&lobby.code, |room| room.persistent = true);
+        assert!(state.create_room_with_host(60, 8, Player::new("alice")).is_err());
+
+        state.remove_player_from_room(&lobby.code, &lobby.host_id).unwrap();
+
+        assert!(state.get_room(&lobby.code).is_some());
+        assert!(state.create_room_with_host(60, 8, Player::new("alice")).is_ok());
+        // Deleting the kept lobby later doesn't hand back a second slot
+        state.remove_room(&lobby.code);
+        assert!(state.create_room_with_host(60, 8, Player::new("alice")).is_err());
+    }
+
+    #[test]
+    fn test_room_events_recorded_in_order() {
+        let state = AppState::with_config(ServerConfig { debug_endpoints: true, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        state.remove_player_from_room(&room_code, &ids[1]).unwrap();
+
+        let events: Vec<RoomEventKind> = state.room_events(&room_code).into_iter().map(|e| e.event).collect();
+        assert_eq!(events, vec![
+            RoomEventKind::PlayerJoined { player_id: ids[0], username: "a".to_string() },
+            RoomEventKind::PlayerJoined { player_id: ids[1], username: "b".to_string() },
+            RoomEventKind::PlayerLeft { player_id: ids[1] },
+        ]);
+    }
+
+    #[test]
+    fn test_mid_game_joiner_is_appended_to_draw_order() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.end_round(&room_code).unwrap();
+
+        // An early joined_at (clock skew, rejoin) must not jump the queue
+        let mut late = Player::new("d");
+        late.joined_at = Utc::now() - chrono::Duration::hours(1);
+        let late_id = late.id;
+        state.add_player_to_room(&room_code, late).unwrap();
+
+        let mut drawers = Vec::new();
+        for _ in 0..3 {
+            drawers.push(state.end_round(&room_code).unwrap().next_drawer.unwrap().id);
+        }
+        assert_eq!(drawers, vec![ids[2], late_id, ids[0]]);
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.draw_order, vec![ids[0], ids[1], ids[2], late_id]);
+        assert_eq!(room.cycle_number, 2);
+    }
+
+    #[test]
+    fn test_leave_mid_cycle_does_not_end_the_cycle_early() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c", "d"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.end_round(&room_code).unwrap();
+        state.end_round(&room_code).unwrap();
+        state.remove_player_from_room(&room_code, &ids[1]).unwrap();
+
+        // C's round ends with D still to draw in this cycle
+        let outcome = state.end_round(&room_code).unwrap();
+        assert_eq!(outcome.next_drawer.unwrap().id, ids[3]);
+        assert_eq!(state.get_room(&room_code).unwrap().cycle_number, 1);
+
+        let outcome = state.end_round(&room_code).unwrap();
+        assert_eq!(outcome.next_drawer.unwrap().id, ids[0]);
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!((room.cycle_number, room.round_number), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_never_draining_connection_is_evicted_when_queue_fills() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (tx, _rx) = connection_channel();
+        state.add_connection(ids[0], room_code.clone(), tx.clone());
+
+        for _ in 0..SEND_QUEUE_CAPACITY {
+            state.broadcast_to_room(&room_code, Message::Text("tick".to_string()));
+        }
+        let not_yet = tokio::time::timeout(Duration::from_millis(10), tx.evicted()).await;
+        assert!(not_yet.is_err());
+
+        state.broadcast_to_room(&room_code, Message::Text("overflow".to_string()));
+        let evicted = tokio::time::timeout(Duration::from_millis(10), tx.evicted()).await;
+        assert!(evicted.is_ok());
+    }
+
+    #[test]
+    fn test_hidden_drawer_is_masked_for_guessers() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.reveal_drawer = false;
+            room.players.get_mut(&ids[0]).unwrap().state = PlayerState::Drawing;
+        });
+        let room = state.get_room(&room_code).unwrap();
+
+        let guesser_view = AppState::room_view_for(&room, &ids[1]);
+        assert_eq!(guesser_view.current_drawer, None);
+        assert!(guesser_view.players.values().all(|p| p.state != PlayerState::Drawing));
+        assert_eq!(AppState::room_view_for(&room, &ids[0]).current_drawer, Some(ids[0]));
+    }
+
+    #[test]
+    fn test_first_place_tie_starts_sudden_death_instead_of_ending() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["alice", "bob", "carol"]);
+        start_round(&state, &room_code, ids[2], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.sudden_death = true;
+            room.max_rounds = 1;
+            room.draw_order = ids.clone();
+            room.players.get_mut(&ids[0]).unwrap().score = 200;
+            room.players.get_mut(&ids[1]).unwrap().score = 200;
+            room.players.get_mut(&ids[2]).unwrap().score = 50;
+        });
+
+        let outcome = state.end_round(&room_code).unwrap();
+        assert!(!outcome.game_over);
+        assert_eq!(outcome.sudden_death_players, vec![ids[0], ids[1]]);
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.game_state, GameState::Playing);
+        assert_eq!(room.current_drawer, Some(ids[2]));
+
+        // The first contender to guess breaks the tie and the game ends
+        state.with_room_mut(&room_code, |room| {
+            room.word = Some("apple".to_string());
+            room.current_round_guesses.push(guess_for(ids[1]));
+        });
+        let outcome = state.end_round(&room_code).unwrap();
+        assert!(outcome.game_over);
+        assert_eq!(outcome.rankings[0].player_id, ids[1]);
+        assert_eq!(outcome.rankings[0].score, 201);
+    }
+
+    #[test]
+    fn test_game_ends_once_wall_clock_limit_passes() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.max_rounds = 3;
+            room.max_game_duration_secs = Some(600);
+            room.game_started_at = Some(Utc::now() - chrono::Duration::seconds(60));
+        });
+        assert!(state.end_round_if_game_expired(&room_code).is_none());
+
+        // Pretend the game started long enough ago that the limit has passed mid-cycle
+        state.with_room_mut(&room_code, |room| {
+            room.game_started_at = Some(Utc::now() - chrono::Duration::seconds(601));
+        });
+        let outcome = state.end_round_if_game_expired(&room_code).unwrap();
+        assert!(outcome.game_over);
+        assert_eq!(state.get_room(&room_code).unwrap().game_state, GameState::Finished);
+    }
+
+    #[test]
+    fn test_admin_observer_sees_word_hidden_from_guessers() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut guesser_rx = crate::test_support::connect(&state, &room_code, ids[1]);
+        let (tx, mut observer_rx) = connection_channel();
+        state.add_observer(room_code.clone(), tx);
+
+        state.broadcast_room_state_filtered(&room_code);
+
+        let word_seen = |messages: Vec<crate::models::ServerMessage>| match messages.as_slice() {
+            [crate::models::ServerMessage::GameStateUpdate { room }] => room.word.clone(),
+            other => panic!("expected one state update, got {:?}", other),
+        };
+        assert_eq!(word_seen(crate::test_support::drain(&mut observer_rx)), Some("apple".to_string()));
+        assert_eq!(word_seen(crate::test_support::drain(&mut guesser_rx)), None);
+    }
+
+    #[test]
+    fn test_persistent_room_survives_last_player_leaving() {
+        let state = AppState::new();
+        let (persistent_code, persistent_ids) = room_with_players(&state, &["a", "b"]);
+        let (normal_code, normal_ids) = room_with_players(&state, &["c"]);
+        state.with_room_mut(&persistent_code, |room| room.persistent = true);
+        start_round(&state, &persistent_code, persistent_ids[0], "apple");
+        let (tx, _rx) = connection_channel();
+        state.add_connection(persistent_ids[0], persistent_code.clone(), tx);
+
+        for id in &persistent_ids {
+            state.remove_player_from_room(&persistent_code, id).unwrap();
+        }
+        state.remove_player_from_room(&normal_code, &normal_ids[0]).unwrap();
+
+        let room = state.get_room(&persistent_code).expect("persistent room is kept");
+        assert_eq!(room.game_state, GameState::Waiting);
+        assert!(room.word.is_none() && room.current_drawer.is_none());
+        assert!(!state.connections.iter().any(|c| c.room_code == persistent_code));
+        assert!(state.get_room(&normal_code).is_none());
+    }
+
+    #[test]
+    fn test_rounds_after_current_mid_cycle_and_at_boundaries() {
+        let state = AppState::new();
+        let (room_code, _) = room_with_players(&state, &["a", "b", "c"]);
+        let mut room = state.get_room(&room_code).unwrap();
+        room.draw_order = room.players_by_join_time();
+        room.max_rounds = 2;
+        let remaining_at = |room: &mut Room, cycle, round| {
+            room.cycle_number = cycle;
+            room.round_number = round;
+            room.rounds_after_current()
+        };
+
+        assert_eq!(remaining_at(&mut room, 1, 1), 5); // first round of the game
+        assert_eq!(remaining_at(&mut room, 1, 2), 4); // mid-cycle
+        assert_eq!(remaining_at(&mut room, 1, 3), 3); // last round of a cycle
+        assert_eq!(remaining_at(&mut room, 2, 1), 2); // first round of the last cycle
+        assert_eq!(remaining_at(&mut room, 2, 3), 0); // final round
+        assert_eq!(remaining_at(&mut room, 3, 1), 0); // past the end never underflows
+        room.draw_order.truncate(2); // a player left after taking their turn
+        assert_eq!(remaining_at(&mut room, 1, 3), 2);
+    }
+
+    #[test]
+    fn test_rounds_remaining_counts_down_across_a_cycle_boundary() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.max_rounds = 2;
+            room.draw_order = room.players_by_join_time();
+        });
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            state.end_round(&room_code).unwrap();
+            let room = state.get_room(&room_code).unwrap();
+            seen.push((room.cycle_number, room.round_number, room.rounds_remaining));
+            state.with_room_mut(&room_code, |room| room.word = Some("apple".to_string()));
+        }
+        assert_eq!(seen, vec![(1, 2, 2), (2, 1, 1), (2, 2, 0)]);
+    }
+}
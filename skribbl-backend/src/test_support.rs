@@ -0,0 +1,104 @@
+// Shared helpers for handler and state tests
+use crate::models::{GameState, Player, ServerMessage};
+use crate::state::AppState;
+use axum::extract::ws::Message;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// State whose rounds roll straight into the next, for tests that follow the rotation
+pub fn without_intermission() -> AppState {
+    AppState::with_config(crate::config::ServerConfig { round_intermission_secs: 0, ..crate::config::ServerConfig::default() })
+}
+
+/// Create a room whose host is the first username; returns the room code and player ids in join order
+pub fn room_with_players(state: &AppState, usernames: &[&str]) -> (String, Vec<Uuid>) {
+    let room_code = state.generate_room_code();
+    let players: Vec<Player> = usernames
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let mut p = Player::new(name);
+            // Distinct join times keep the drawer rotation deterministic
+            p.joined_at = chrono::Utc::now() + chrono::Duration::milliseconds(i as i64);
+            p
+        })
+        .collect();
+
+    state.create_room(room_code.clone(), 60, 8, players[0].id).unwrap();
+    for p in &players {
+        state.add_player_to_room(&room_code, p.clone()).unwrap();
+    }
+    (room_code, players.iter().map(|p| p.id).collect())
+}
+
+/// A two-player room ("artist", "guesser") in a round where the artist draws `word`;
+/// returns the room code, both ids and the guesser's connection
+pub fn round_with_guesser(state: &AppState, word: &str) -> (String, Vec<Uuid>, mpsc::Receiver<Message>) {
+    let (room_code, ids) = room_with_players(state, &["artist", "guesser"]);
+    start_round(state, &room_code, ids[0], word);
+    let rx = connect(state, &room_code, ids[1]);
+    (room_code, ids, rx)
+}
+
+/// Put the room into an active round with the given drawer and word
+pub fn start_round(state: &AppState, room_code: &str, drawer: Uuid, word: &str) {
+    let mut room = state.get_room(room_code).unwrap();
+    room.game_state = GameState::Playing;
+    room.current_drawer = Some(drawer);
+    room.word = Some(word.to_string());
+    room.round_number = 1;
+    room.cycle_number = 1;
+    room.round_start_time = Some(chrono::Utc::now());
+    room.winners = vec![drawer];
+    state.update_room(room_code, room).unwrap();
+}
+
+/// Register a WebSocket connection for the player and return its receiving end
+pub fn connect(state: &AppState, room_code: &str, player_id: Uuid) -> mpsc::Receiver<Message> {
+    let (tx, rx) = crate::state::connection_channel();
+    state.add_connection(player_id, room_code.to_string(), tx);
+    rx
+}
+
+/// Drain every queued message for a connection
+pub fn drain(rx: &mut mpsc::Receiver<Message>) -> Vec<ServerMessage> {
+    let mut messages = Vec::new();
+    while let Ok(Message::Text(text)) = rx.try_recv() {
+        messages.push(serde_json::from_str(&text).expect("server sent invalid ServerMessage"));
+    }
+    messages
+}
+
+/// Drain a connection, keeping a value from each message that matches the pattern, in order
+macro_rules! drained {
+    ($rx:expr, $pattern:pat => $value:expr) => {
+        $crate::test_support::drain($rx)
+            .into_iter()
+            .filter_map(|m| match m {
+                $pattern => Some($value),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+}
+pub(crate) use drained;
+
+/// The blanked-out word patterns a connection was sent
+pub fn hint_patterns(rx: &mut mpsc::Receiver<Message>) -> Vec<String> {
+    drained!(rx, ServerMessage::WordHint { pattern } => pattern)
+}
+
+/// The draw orders a connection was sent
+pub fn draw_orders(rx: &mut mpsc::Receiver<Message>) -> Vec<Vec<Uuid>> {
+    drained!(rx, ServerMessage::DrawOrder { order } => order)
+}
+
+/// The word-selection countdown ticks a connection was sent
+pub fn countdown_ticks(rx: &mut mpsc::Receiver<Message>) -> Vec<u32> {
+    drained!(rx, ServerMessage::SelectingWord { seconds_remaining, .. } => seconds_remaining)
+}
+
+/// The first set of word choices a connection was offered
+pub fn word_choices(rx: &mut mpsc::Receiver<Message>) -> Option<Vec<String>> {
+    drained!(rx, ServerMessage::WordChoices { words } => words).into_iter().next()
+}
@@ -16,6 +16,11 @@ pub fn convert_color(color_str: &str) -> Color {
     }
 }
 
+/// Canonical form of a client color string, so equal colors compare equal once stored
+pub fn normalize_color_hex(color_str: &str) -> String {
+    color_str.trim().to_lowercase()
+}
+
 /// Convert frontend brush size number to backend BrushSize enum
 pub fn convert_brush_size(size: u32) -> BrushSize {
     match size {
@@ -1,7 +1,6 @@
-use crate::models::ChatMessage;
-use crate::state::AppState;
+use crate::models::{ChatMessage, DrawerChangeReason, PlayerState, Room};
+use crate::state::{AppState, ConnectionSender};
 use axum::extract::ws::Message;
-use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
 /// Handle chat messages
@@ -11,15 +10,42 @@ pub async fn handle_chat(
     message: &str,
     player_id: Uuid,
     username: &str,
-    _tx: &UnboundedSender<Message>,
+    tx: &ConnectionSender,
 ) {
+    let mut is_spectator = false;
+    let mut is_muted = false;
     // Only non-winners/non-artist messages are evaluated as guesses.
     if let Some(room) = state.get_room(room_code) {
+        is_muted = room.muted.contains(&player_id);
+        is_spectator = room.players.get(&player_id).is_some_and(|p| p.state == PlayerState::Spectator);
+        if is_spectator {
+            // Spectators never guess, but one typing the word by chance mustn't reveal it either
+            let rejection = if !room.allow_spectator_chat {
+                Some("Spectator chat is turned off in this room")
+            } else if room.word.as_deref().is_some_and(|word| {
+                crate::words::is_word_match(message, word, room.guess_matching) || looks_like_word_leak(message, word)
+            }) {
+                Some("Message hidden: it looks like it gives away the word")
+            } else {
+                None
+            };
+            if let Some(reason) = rejection {
+                let error_msg = crate::models::ServerMessage::Error { message: reason.to_string(), severity: crate::models::ErrorSeverity::Warning };
+                if let Ok(json) = serde_json::to_string(&error_msg) {
+                    let _ = tx.send(Message::Text(json));
+                }
+                return;
+            }
+        }
         let is_artist = room.current_drawer.map(|d| d == player_id).unwrap_or(false);
         let is_winner = room.winners.contains(&player_id);
 
         // Winners (including artist) always route to winners-only chat and never trigger guess logic
         if is_artist || is_winner {
+            if is_muted {
+                send_muted_error(tx);
+                return;
+            }
             // Winners-only message path
             let chat_msg = ChatMessage {
                 id: Uuid::new_v4(),
@@ -28,13 +54,19 @@ pub async fn handle_chat(
                 message: message.to_string(),
                 timestamp: chrono::Utc::now(),
                 is_winners_only: true,
+                is_spectator: false,
             };
-            if let Some(mut r) = state.get_room(room_code) {
-                r.chat_messages.push(chat_msg.clone());
-                if r.chat_messages.len() > 10 { r.chat_messages.remove(0); }
-                let _ = state.update_room(room_code, r.clone());
-                // Server-side filtered room state
-                state.broadcast_room_state_filtered(room_code);
+            // The ChatMessage below carries the change, so no full-state broadcast
+            let sent = state.with_room_mut(room_code, |r| {
+                let allowed = take_winners_chat_slot(r, player_id);
+                if allowed {
+                    push_chat_message(r, chat_msg.clone());
+                }
+                allowed
+            });
+            if sent == Some(false) {
+                send_winners_chat_capped(state, player_id);
+                return;
             }
             // Winners-only message is only sent to winners
             let server_msg = crate::models::ServerMessage::ChatMessage { message: chat_msg };
@@ -45,16 +77,58 @@ pub async fn handle_chat(
             return;
         }
 
+        // Non-winner messages are guesses while a word is active; guesses inside the
+        // per-player cooldown are dropped silently so short words can't be brute-forced
+        if !is_spectator && room.word.is_some() && !state.try_start_guess(player_id) {
+            println!("Guess from {} in room {} dropped (cooldown)", username, room_code);
+            return;
+        }
+
         // Non-winner: check if this is a correct guess
-        if let Some(current_word) = &room.word {
-            let is_correct_guess = message.trim().to_lowercase() == current_word.to_lowercase();
-            if is_correct_guess {
+        if !is_spectator && let Some(current_word) = &room.word {
+            let is_correct_guess = crate::words::is_word_match(message, current_word, room.guess_matching);
+
+            // Spelling the word out or scrambling it only helps other players, so hold it back
+            if !is_correct_guess && looks_like_word_leak(message, current_word) {
+                println!("Suspected word leak in room {} from {}: {}", room_code, username, message);
+                let error_msg = crate::models::ServerMessage::Error {
+                    message: "Message hidden: it looks like it gives away the word".to_string(),
+                    severity: crate::models::ErrorSeverity::Info,
+                };
+                if let Ok(json) = serde_json::to_string(&error_msg) {
+                    let _ = tx.send(Message::Text(json));
+                }
+                return;
+            }
+
+            // During the warmup guesses never count. Other messages fall through to regular
+            // chat, but a matching one is held back so it doesn't reveal the word to the room.
+            if !room.guessing_open(chrono::Utc::now()) {
+                if is_correct_guess {
+                    let error_msg = crate::models::ServerMessage::Error {
+                        message: "Guessing hasn't opened yet".to_string(),
+                        severity: crate::models::ErrorSeverity::Info,
+                    };
+                    if let Ok(json) = serde_json::to_string(&error_msg) {
+                        let _ = tx.send(Message::Text(json));
+                    }
+                    return;
+                }
+            } else if is_correct_guess {
                 handle_correct_guess(state, room_code, message, player_id, username).await;
                 return;
+            } else {
+                send_close_guess_feedback(state, room_code, message, current_word, room.guess_matching, player_id, tx);
             }
         }
     }
     
+    // Muted players can still guess (handled above), but their chat goes nowhere
+    if is_muted {
+        send_muted_error(tx);
+        return;
+    }
+
     // Create chat message
     let chat_msg = ChatMessage {
         id: Uuid::new_v4(),
@@ -63,23 +137,18 @@ pub async fn handle_chat(
         message: message.to_string(),
         timestamp: chrono::Utc::now(),
         is_winners_only: false, // Regular chat messages are visible to all
+        is_spectator,
     };
     
-    // Store message in room's chat history (keep last 10)
-    if let Some(mut room) = state.get_room(room_code) {
-        room.chat_messages.push(chat_msg.clone());
-        if room.chat_messages.len() > 10 {
-            room.chat_messages.remove(0); // Remove oldest message
-        }
-        
-        // Update room with new chat history
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room chat history: {}", e);
-        }
-        
-        // Server-side filtered room state to all
-        state.broadcast_room_state_filtered(room_code);
-    }
+    // Store message in room's chat history (keep last 10). The ChatMessage broadcast below
+    // carries the change, so clients don't get a full room state for plain chat.
+    let Some(hidden_from_drawer) = state.with_room_mut(room_code, |room| {
+        push_chat_message(room, chat_msg.clone());
+        room.current_drawer.filter(|drawer| AppState::guesses_hidden_from(room, drawer).is_some())
+    }) else {
+        println!("Failed to update room chat history: room {} not found", room_code);
+        return;
+    };
     
     // Broadcast chat message
     let server_msg = crate::models::ServerMessage::ChatMessage {
@@ -87,12 +156,117 @@ pub async fn handle_chat(
     };
     
     if let Ok(json) = serde_json::to_string(&server_msg) {
-        state.broadcast_to_room(room_code, Message::Text(json));
+        match hidden_from_drawer {
+            Some(drawer_id) => state.broadcast_to_room_excluding(room_code, Message::Text(json), drawer_id),
+            None => state.broadcast_to_room(room_code, Message::Text(json)),
+        }
     }
     
     println!("Chat message in room {} from {}: {}", room_code, username, message);
 }
 
+// Wrong guesses this many edits from the word count as near misses
+const CLOSE_GUESS_DISTANCE: usize = 1;
+
+/// Count a wrong guess toward the player's near-miss streak, or end the streak if it was far
+/// off, and privately tell them when they're close. Enough near misses in a row get a
+/// stronger nudge. The guess still goes to chat as usual.
+fn send_close_guess_feedback(
+    state: &AppState,
+    room_code: &str,
+    guess: &str,
+    word: &str,
+    matching: crate::models::GuessMatching,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let near_miss = crate::words::guess_distance(guess, word, matching) <= CLOSE_GUESS_DISTANCE;
+    let streak = state
+        .with_room_mut(room_code, |room| {
+            if !near_miss {
+                room.near_miss_streaks.remove(&player_id);
+                return 0;
+            }
+            let streak = room.near_miss_streaks.entry(player_id).or_insert(0);
+            *streak += 1;
+            *streak
+        })
+        .unwrap_or(0);
+    if streak == 0 {
+        return;
+    }
+    let escalate_after = state.config.very_close_streak;
+    let message = if escalate_after > 0 && streak >= escalate_after { "Very close!" } else { "You're close!" };
+    let close_msg = crate::models::ServerMessage::CloseGuess {
+        guess: guess.to_string(),
+        streak,
+        message: message.to_string(),
+    };
+    if let Ok(json) = serde_json::to_string(&close_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
+/// Tell a muted player their message wasn't delivered
+fn send_muted_error(tx: &ConnectionSender) {
+    let error_msg = crate::models::ServerMessage::Error {
+        message: "You have been muted by the host".to_string(),
+        severity: crate::models::ErrorSeverity::Warning,
+    };
+    if let Ok(json) = serde_json::to_string(&error_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
+/// Whether a message that isn't a plain guess still gives away the word: the word spelled
+/// with separators ("a-p-p-l-e", "a p p l e") or an exact anagram of it ("elppa")
+fn looks_like_word_leak(message: &str, word: &str) -> bool {
+    let letters = |s: &str| -> Vec<char> {
+        s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+    };
+    let stripped = letters(message);
+    let target = letters(word);
+    if target.len() < 2 || stripped.len() != target.len() {
+        return false;
+    }
+    if stripped == target {
+        return true;
+    }
+    let (mut sorted_message, mut sorted_word) = (stripped, target);
+    sorted_message.sort_unstable();
+    sorted_word.sort_unstable();
+    sorted_message == sorted_word
+}
+
+/// Count one winners-chat message against the player's per-round budget; false once it's spent
+fn take_winners_chat_slot(room: &mut Room, player_id: Uuid) -> bool {
+    let sent = room.winners_chat_sent.entry(player_id).or_insert(0);
+    if room.winners_chat_cap != 0 && *sent >= room.winners_chat_cap {
+        return false;
+    }
+    *sent += 1;
+    true
+}
+
+/// Tell a winner their message was dropped for going over the round's winners-chat cap
+fn send_winners_chat_capped(state: &AppState, player_id: Uuid) {
+    let error_msg = crate::models::ServerMessage::Error {
+        message: "You've reached this round's winners chat limit".to_string(),
+        severity: crate::models::ErrorSeverity::Warning,
+    };
+    if let Ok(json) = serde_json::to_string(&error_msg) {
+        state.send_to_player(&player_id, Message::Text(json));
+    }
+}
+
+/// Append a message to the room's chat history, keeping only the last 10
+fn push_chat_message(room: &mut Room, chat_msg: ChatMessage) {
+    room.chat_messages.push(chat_msg);
+    if room.chat_messages.len() > 10 {
+        room.chat_messages.remove(0); // Remove oldest message
+    }
+}
+
 /// Handle correct word guesses
 async fn handle_correct_guess(
     state: &AppState,
@@ -101,265 +275,151 @@ async fn handle_correct_guess(
     player_id: Uuid,
     username: &str,
 ) {
-    if let Some(mut room) = state.get_room(room_code) {
+    // Record the guess under the room lock so concurrent correct guesses can't overwrite each other
+    let recorded = state.with_room_mut(room_code, |room| {
+        // The drawer knows the word; matching it must never score or end the round
+        if room.current_drawer == Some(player_id) {
+            return None;
+        }
+
+        // Only the tied leaders may guess in sudden death
+        if !room.sudden_death_players.is_empty() && !room.sudden_death_players.contains(&player_id) {
+            return None;
+        }
+
+        // The round may have moved on since the guess was checked
+        let canonical_word = room.word.clone().filter(|w| crate::words::is_word_match(word, w, room.guess_matching))?;
+        let guessing_open = room.guessing_open(chrono::Utc::now());
+
         // Check if this player already guessed correctly
         let already_guessed = room.current_round_guesses
             .iter()
             .any(|guess| guess.player_id == player_id);
-        
-        if already_guessed {
-            return; // Player already guessed correctly
+
+        if !guessing_open || already_guessed {
+            return None;
         }
-        
+
         // Calculate time remaining and normalized time
         let current_time = chrono::Utc::now();
         let round_start = room.round_start_time.unwrap_or(current_time);
         let elapsed = current_time.signed_duration_since(round_start).num_seconds() as u32;
         let time_remaining = room.round_duration.saturating_sub(elapsed);
         let normalized_time = (time_remaining as f64 / room.round_duration as f64).clamp(0.0, 1.0);
-        
-        // Create guess record
-        let guess = crate::models::Guess {
+
+        // The guess list is emptied at every round start, so this is the round's first correct guess
+        let first_blood = room.current_round_guesses.is_empty();
+
+        // Add to room's current round guesses
+        room.current_round_guesses.push(crate::models::Guess {
             player_id,
             username: username.to_string(),
-            word: word.to_string(),
+            word: canonical_word.clone(),
             timestamp: current_time,
             time_remaining,
             normalized_time,
-        };
-        
-        // Add to room's current round guesses
-        room.current_round_guesses.push(guess.clone());
-        
+        });
+
         // Add player to winners list (if not already there)
         if !room.winners.contains(&player_id) {
             room.winners.push(player_id);
         }
-        
-        // Update room in state
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room with guess: {}", e);
-            return;
-        }
-        
-        // Broadcast correct guess event to everyone (no chat leakage)
+
+        // In sudden death the first correct guess settles the game
+        let everyone_guessed = !room.sudden_death_players.is_empty()
+            || room.current_round_guesses.len() >= room.potential_guessers();
+        let (guessed, remaining) = room.guess_progress();
+        let progress = crate::models::ServerMessage::GuessProgress { guessed, remaining };
+        Some((room.players.get(&player_id).cloned(), canonical_word, progress, everyone_guessed, room.round_token, round_start, first_blood))
+    }).flatten();
+
+    // Reveal the word as stored, not as this player happened to type it
+    let Some((player, word, progress, everyone_guessed, round_token, round_start, first_blood)) = recorded else {
+        return; // Player already guessed correctly or the round is over
+    };
+
+    // Broadcast correct guess event to everyone (no chat leakage)
+    if let Some(player) = player {
         let correct_guess_msg = crate::models::ServerMessage::CorrectGuess {
-            player: room.players.get(&player_id).unwrap().clone(),
-            word: word.to_string(),
+            player: player.clone(),
+            word: word.clone(),
         };
         if let Ok(json) = serde_json::to_string(&correct_guess_msg) {
             state.broadcast_to_room(room_code, Message::Text(json));
         }
-        
-        // Broadcast filtered room state reflecting new winner
-        state.broadcast_room_state_filtered(room_code);
-        
-        println!("Correct guess in room {} by {}: {}", room_code, username, word);
-        
-        // Check if everyone has guessed correctly
-        let potential_guessers = room.players.len() - 1; // Exclude artist
-        if room.current_round_guesses.len() >= potential_guessers {
-            // Everyone guessed correctly - end round
-            handle_round_end(state, room_code).await;
+        if first_blood {
+            let first_blood_msg = crate::models::ServerMessage::FirstBlood { player };
+            if let Ok(json) = serde_json::to_string(&first_blood_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
         }
     }
-}
-
-/// Handle round end when everyone guesses correctly
-async fn handle_round_end(state: &AppState, room_code: &str) {
-    if let Some(room) = state.get_room(room_code) {
-        // Calculate scores using the scoring system
-        let potential_guessers = room.players.len() - 1;
-        let artist_streak = room.players.get(&room.current_drawer.unwrap_or_default())
-            .map(|p| p.artist_streak)
-            .unwrap_or(0);
-        
-        let scores = crate::scoring::calculate_round_scores(
-            room.round_number,
-            &room.word.unwrap_or_default(),
-            room.round_duration,
-            room.current_round_guesses.clone(),
-            potential_guessers as u32,
-            artist_streak,
-        );
-        
-        // Broadcast round scores
-        let round_scores_msg = crate::models::ServerMessage::RoundScores {
-            scores: scores.clone(),
-        };
-        
-        if let Ok(json) = serde_json::to_string(&round_scores_msg) {
-            state.broadcast_to_room(room_code, Message::Text(json));
-        }
-        
-        // Update player scores and artist streaks
-        update_player_scores(state, room_code, &scores).await;
-        
-        // Rotate drawer and reset round state
-        if let Some(mut r2) = state.get_room(room_code) {
-            // Determine ordered players by joined_at
-            let mut ordered: Vec<_> = r2.players.values().cloned().collect();
-            ordered.sort_by(|a, b| a.joined_at.cmp(&b.joined_at));
-            let current = r2.current_drawer;
-            let next_drawer = if let Some(cur) = current {
-                let idx = ordered.iter().position(|p| p.id == cur).unwrap_or(0);
-                let next_idx = (idx + 1) % ordered.len();
-                ordered[next_idx].id
-            } else {
-                // If none set, pick first
-                ordered.first().map(|p| p.id).unwrap_or_else(|| cur_default())
-            };
 
-            // Check if we're starting a new cycle (back to first player)
-            let is_new_cycle = if let Some(cur) = current {
-                let cur_idx = ordered.iter().position(|p| p.id == cur).unwrap_or(0);
-                let next_idx = (cur_idx + 1) % ordered.len();
-                let will_be_new_cycle = next_idx == 0; // If next drawer is first player, it's a new cycle
-                println!("Cycle check: current_idx={}, next_idx={}, players_total={}, will_be_new_cycle={}", 
-                        cur_idx, next_idx, ordered.len(), will_be_new_cycle);
-                will_be_new_cycle
-            } else {
-                false
-            };
+    if let Ok(json) = serde_json::to_string(&progress) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
 
-            println!("Before update - Round: {}, Cycle: {}, Max Cycles: {}", 
-                    r2.round_number, r2.cycle_number, r2.max_rounds);
+    // Broadcast filtered room state reflecting new winner
+    state.broadcast_room_state_filtered(room_code);
 
-            // Increment round number and cycle number if needed
-            if is_new_cycle {
-                r2.cycle_number = r2.cycle_number.saturating_add(1);
-                r2.round_number = 1; // Reset to 1 for new cycle
-                println!("New cycle started! Cycle {} of {} (max cycles)", r2.cycle_number, r2.max_rounds);
-            } else {
-                r2.round_number = r2.round_number.saturating_add(1); // Increment round within cycle
-            }
-            
-            println!("After update - Round: {}, Cycle: {}, Max Cycles: {}", 
-                    r2.round_number, r2.cycle_number, r2.max_rounds);
-            
-            // CRITICAL FIX: Additional cycle progression logic
-            // Ensure cycle increments every N rounds where N = number of players
-            let players_count = ordered.len() as u32;
-            if r2.round_number > players_count {
-                // We've exceeded the number of players in this cycle, force a new cycle
-                r2.cycle_number = r2.cycle_number.saturating_add(1);
-                r2.round_number = 1; // Reset to 1 for new cycle
-                println!("Force new cycle! Round {} > {} players, Cycle {} of {} (max cycles)", 
-                        r2.round_number, players_count, r2.cycle_number, r2.max_rounds);
-            }
-            
-            // Enhanced debugging: Log the complete state after all updates
-            println!("Final state after cycle logic:");
-            println!("   - Players count: {}", players_count);
-            println!("   - Round number: {}", r2.round_number);
-            println!("   - Cycle number: {}", r2.cycle_number);
-            println!("   - Max cycles: {}", r2.max_rounds);
-            println!("   - Is new cycle: {}", is_new_cycle);
-            
-            // Log round completion
-            let unknown_str = "Unknown".to_string();
-            let none_str = "None".to_string();
-            
-            let current_drawer_name = if let Some(cur_id) = current {
-                r2.players.get(&cur_id).map(|p| &p.username).unwrap_or(&unknown_str)
-            } else {
-                &none_str
-            };
-            let next_drawer_name = if let Some(idx) = ordered.iter().position(|p| p.id == next_drawer) {
-                ordered.get(idx).map(|p| &p.username).unwrap_or(&unknown_str)
-            } else {
-                &unknown_str
-            };
-            println!(
-                "Round {} complete. Current drawer: {}, Next drawer: {}, Cycle: {} of {}",
-                r2.round_number, current_drawer_name, next_drawer_name, r2.cycle_number, r2.max_rounds
-            );
-            
-            // Reset per-round state
-            r2.current_drawer = Some(next_drawer);
-            r2.word = None;
-            r2.round_start_time = None;
-            r2.round_end_time = None;
-            r2.current_round_guesses.clear();
-            r2.drawing_paths.clear();
-            r2.winners.clear();
-            // Artist is always a winner
-            r2.winners.push(next_drawer);
-
-            let _ = state.update_room(room_code, r2.clone());
-
-            // Announce next drawer
-            if let Some(drawer_player) = r2.players.get(&next_drawer) {
-                let next_msg = crate::models::ServerMessage::RoundStart { room_code: room_code.to_string(), drawer: drawer_player.clone() };
-                if let Ok(json) = serde_json::to_string(&next_msg) {
-                    state.broadcast_to_room(room_code, Message::Text(json));
-                }
-            }
+    println!("Correct guess in room {} by {}: {}", room_code, username, word);
 
-            // Check if game should end (max cycles reached)
-            if r2.cycle_number > r2.max_rounds {
-                println!("Game ending: Cycle {} > Max Cycles {} - Game Over!", r2.cycle_number, r2.max_rounds);
-                // Game over - broadcast final scores
-                r2.game_state = crate::models::GameState::Finished;
-                if let Err(e) = state.update_room(room_code, r2.clone()) {
-                    println!("Failed to update room to finished state: {}", e);
-                }
-                
-                let game_end_msg = crate::models::ServerMessage::GameEnded {
-                    final_scores: r2.players.iter().map(|(id, p)| (id.to_string(), p.score)).collect(),
-                };
-                if let Ok(json) = serde_json::to_string(&game_end_msg) {
-                    state.broadcast_to_room(room_code, Message::Text(json));
-                }
-                return; // Don't start next round
-            }
-
-            // Send filtered state so visibility is correct
-            state.broadcast_room_state_filtered(room_code);
-        }
-
-        println!("Round ended in room {} with scores: {:?}", room_code, scores);
+    // Everyone guessed correctly - reveal the word and advance after a short pause
+    if everyone_guessed {
+        schedule_all_guessed_advance(state, room_code, &word, round_token, round_start);
     }
 }
 
-fn cur_default() -> uuid::Uuid { uuid::Uuid::nil() }
+/// Reveal the word, then end the round once the configured delay has passed, and no sooner
+/// than min_round_secs after the round started so clients always get a play window.
+/// The round token makes this a no-op if the backend timer ended the round first.
+fn schedule_all_guessed_advance(
+    state: &AppState,
+    room_code: &str,
+    word: &str,
+    round_token: u64,
+    round_start: chrono::DateTime<chrono::Utc>,
+) {
+    let elapsed_ms = chrono::Utc::now().signed_duration_since(round_start).num_milliseconds().max(0) as u64;
+    let min_remaining_ms = (state.config.min_round_secs * 1000).saturating_sub(elapsed_ms);
+    let delay_ms = state.config.all_guessed_advance_ms.max(min_remaining_ms);
+    let all_guessed_msg = crate::models::ServerMessage::AllGuessed {
+        word: word.to_string(),
+        advance_in_ms: delay_ms,
+    };
+    if let Ok(json) = serde_json::to_string(&all_guessed_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
 
-/// Update player scores and artist streaks after round end
-pub(crate) async fn update_player_scores(state: &AppState, room_code: &str, scores: &crate::models::RoundScores) {
-    if let Some(mut room) = state.get_room(room_code) {
-        // Update guesser scores
-        for (player_id, score) in &scores.guesser_scores {
-            if let Some(player) = room.players.get_mut(player_id) {
-                player.score += score;
-            }
-        }
-        
-        // Update artist score and streak
-        if let Some(drawer_id) = room.current_drawer {
-            // Get the potential guessers count before borrowing mutably
-            let potential_guessers = room.players.len() - 1;
-            
-            if let Some(player) = room.players.get_mut(&drawer_id) {
-                player.score += scores.artist_score;
-                
-                // Check if artist streak should increment before borrowing mutably
-                let should_increment = crate::scoring::should_increment_artist_streak(
-                    &scores.correct_guesses,
-                    scores.round_duration,
-                    potential_guessers as u32,
-                );
-                
-                player.artist_streak = crate::scoring::update_artist_streak(
-                    player.artist_streak,
-                    should_increment,
-                );
-            }
-        }
-        
-        // Update room in state
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room with scores: {}", e);
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        if let Some(outcome) = state.end_round_if_current(&room_code, round_token) {
+            super::rooms::broadcast_round_outcome(&state, &room_code, &outcome, DrawerChangeReason::Rotation);
         }
+    });
+}
+
+/// Send recent chat to one player, masked the way their room state is: winners-only
+/// messages are left out unless they've guessed the word or are drawing
+pub async fn handle_request_chat_history(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let message = match state.get_room(room_code) {
+        Some(room) if room.players.contains_key(&player_id) => crate::models::ServerMessage::ChatHistory {
+            messages: AppState::room_view_for(&room, &player_id).chat_messages,
+        },
+        _ => crate::models::ServerMessage::Error {
+            message: "You are not in this room".to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
+        },
+    };
+    if let Ok(json) = serde_json::to_string(&message) {
+        let _ = tx.send(Message::Text(json));
     }
 }
 
@@ -370,6 +430,7 @@ pub async fn handle_winners_chat(
     message: &str,
     player_id: Uuid,
     username: &str,
+    tx: &ConnectionSender,
 ) {
     // Check if this player is a winner (has guessed correctly or is the artist)
     if let Some(room) = state.get_room(room_code) {
@@ -380,6 +441,11 @@ pub async fn handle_winners_chat(
             println!("Player {} tried to send winners-only message but is not a winner", username);
             return;
         }
+        if room.muted.contains(&player_id) {
+            println!("Dropped winners-only message from muted player {}", username);
+            send_muted_error(tx);
+            return;
+        }
         
         // Create winners-only chat message
         let chat_msg = ChatMessage {
@@ -389,38 +455,35 @@ pub async fn handle_winners_chat(
             message: message.to_string(),
             timestamp: chrono::Utc::now(),
             is_winners_only: true, // This message is only visible to winners
+            is_spectator: false,
         };
         
-        // Store message in room's chat history
-        if let Some(mut room) = state.get_room(room_code) {
-            room.chat_messages.push(chat_msg.clone());
-            if room.chat_messages.len() > 10 {
-                room.chat_messages.remove(0);
+        // Store message in room's chat history, unless the player has used up this round's budget.
+        // The ChatMessage below carries the change, so no full-state broadcast.
+        let stored = state.with_room_mut(room_code, |room| {
+            let allowed = take_winners_chat_slot(room, player_id);
+            if allowed {
+                push_chat_message(room, chat_msg.clone());
             }
-            
-            if let Err(e) = state.update_room(room_code, room.clone()) {
-                println!("Failed to update room chat history: {}", e);
-            }
-            
-            // Broadcast GameStateUpdate so frontend gets updated chat
-            let game_state_msg = crate::models::ServerMessage::GameStateUpdate {
-                room: room.clone(),
-            };
-            
-            if let Ok(json) = serde_json::to_string(&game_state_msg) {
-                state.broadcast_to_room(room_code, Message::Text(json));
+            allowed
+        });
+        match stored {
+            Some(true) => {}
+            Some(false) => {
+                send_winners_chat_capped(state, player_id);
+                return;
             }
+            None => return,
         }
-        
-        // Broadcast winners-only message to all (frontend will filter based on is_winners_only flag)
+
+        // Winners-only message is only sent to winners
         let server_msg = crate::models::ServerMessage::ChatMessage {
             message: chat_msg,
         };
-        
         if let Ok(json) = serde_json::to_string(&server_msg) {
-            state.broadcast_to_room(room_code, Message::Text(json));
+            state.broadcast_to_winners(room_code, Message::Text(json));
         }
-        
+
         println!("Winners-only chat message in room {} from {}: {}", room_code, username, message);
     }
 }
@@ -430,7 +493,7 @@ pub async fn handle_guess(
     _state: &AppState,
     room_code: &str,
     guess: &str,
-    _tx: &UnboundedSender<Message>,
+    _tx: &ConnectionSender,
 ) {
     // TODO: Validate guess against current word
     // TODO: Award points if correct
@@ -438,3 +501,399 @@ pub async fn handle_guess(
     
     println!("Guess in room {}: {}", room_code, guess);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use crate::models::ServerMessage;
+    use crate::test_support::{connect, drain, drained, room_with_players, round_with_guesser, start_round};
+
+    #[tokio::test]
+    async fn test_guess_cooldown_drops_rapid_second_guess() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 500, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "banana", ids[1], "guesser", &tx).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(!room.winners.contains(&ids[1]));
+        let chats: Vec<String> = drained!(&mut rx, ServerMessage::ChatMessage { message } => message.message);
+        assert_eq!(chats, vec!["banana".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_winners_chat_over_round_cap_is_dropped_with_notice() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.winners_chat_cap = 2);
+        let mut artist_rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        for n in 0..3 {
+            handle_winners_chat(&state, &room_code, &format!("msg {}", n), ids[0], "artist", &tx).await;
+        }
+
+        let messages = drain(&mut artist_rx);
+        let delivered = messages.iter().filter(|m| matches!(m, ServerMessage::ChatMessage { .. })).count();
+        assert_eq!(delivered, 2);
+        assert!(matches!(messages.last(), Some(ServerMessage::Error { message, .. }) if message.contains("limit")));
+
+        // A new round brings a fresh budget
+        state.end_round(&room_code);
+        assert!(state.get_room(&room_code).unwrap().winners_chat_sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_winners_chat_reaches_only_winners_and_never_the_word() {
+        let state = AppState::new();
+        let (room_code, ids, mut guesser_rx) = round_with_guesser(&state, "apple");
+        let mut artist_rx = connect(&state, &room_code, ids[0]);
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        handle_winners_chat(&state, &room_code, "nice one", ids[0], "artist", &tx).await;
+
+        assert!(drain(&mut artist_rx).iter().any(|m| matches!(m, ServerMessage::ChatMessage { message } if message.is_winners_only)));
+        assert!(drain(&mut guesser_rx).is_empty());
+
+        // A muted winner is told, the same as in open chat
+        state.with_room_mut(&room_code, |room| room.muted.insert(ids[0]));
+        handle_winners_chat(&state, &room_code, "still here", ids[0], "artist", &tx).await;
+        assert!(drain(&mut artist_rx).is_empty());
+        assert!(matches!(drain(&mut own_rx).as_slice(), [ServerMessage::Error { message, .. }] if message.contains("muted")));
+    }
+
+    #[tokio::test]
+    async fn test_drawer_misses_incorrect_guesses_when_hidden() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser", "other"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.drawer_sees_guesses = false);
+        let mut artist_rx = connect(&state, &room_code, ids[0]);
+        let mut other_rx = connect(&state, &room_code, ids[2]);
+        let (tx, _rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "banana", ids[1], "guesser", &tx).await;
+
+        let is_chat = |m: &ServerMessage| matches!(m, ServerMessage::ChatMessage { message } if message.message == "banana");
+        assert!(!drain(&mut artist_rx).iter().any(is_chat));
+        assert!(drain(&mut other_rx).iter().any(is_chat));
+        let room = state.get_room(&room_code).unwrap();
+        assert!(AppState::room_view_for(&room, &ids[0]).chat_messages.iter().all(|m| m.message != "banana"));
+    }
+
+    #[tokio::test]
+    async fn test_correct_guess_during_warmup_does_not_win() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 0, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.guess_delay_secs = 10);
+        let mut artist_rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+        assert!(!state.get_room(&room_code).unwrap().winners.contains(&ids[1]));
+        // The matching guess isn't echoed to the room either
+        assert!(drain(&mut artist_rx).iter().all(|m| !matches!(m, ServerMessage::ChatMessage { .. })));
+
+        // Once the warmup has passed, the same guess counts
+        state.with_room_mut(&room_code, |room| {
+            room.round_start_time = Some(chrono::Utc::now() - chrono::Duration::seconds(11));
+        });
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+        assert!(state.get_room(&room_code).unwrap().winners.contains(&ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_all_guessed_advances_after_delay() {
+        let state = AppState::with_config(ServerConfig {
+            all_guessed_advance_ms: 200,
+            min_round_secs: 0,
+            ..ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+
+        // Still the same round while the reveal is showing
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.round_token, round_token);
+        assert_eq!(room.word.as_deref(), Some("apple"));
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::AllGuessed { advance_in_ms: 200, .. })));
+
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.round_token, round_token + 1);
+        assert_eq!(room.current_drawer, Some(ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_all_guessed_round_runs_at_least_the_minimum() {
+        let state = AppState::with_config(ServerConfig {
+            all_guessed_advance_ms: 200,
+            min_round_secs: 5,
+            ..ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.round_start_time = Some(chrono::Utc::now() - chrono::Duration::seconds(2));
+        });
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+
+        let advance_in_ms = drain(&mut rx).into_iter().find_map(|m| match m {
+            ServerMessage::AllGuessed { advance_in_ms, .. } => Some(advance_in_ms),
+            _ => None,
+        });
+        assert!(advance_in_ms.is_some_and(|ms| (2_500..=3_000).contains(&ms)), "got {:?}", advance_in_ms);
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        assert_eq!(state.get_room(&room_code).unwrap().round_token, round_token);
+    }
+
+    #[test]
+    fn test_separated_and_scrambled_words_look_like_leaks() {
+        assert!(looks_like_word_leak("a p p l e", "apple"));
+        assert!(looks_like_word_leak("A-P-P-L-E", "apple"));
+        assert!(looks_like_word_leak("elppa", "apple"));
+        assert!(looks_like_word_leak("apple", "apple")); // callers only check non-matching guesses
+        assert!(!looks_like_word_leak("apples", "apple"));
+        assert!(!looks_like_word_leak("a banana", "apple"));
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_near_misses_escalate_close_feedback() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 0, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, mut own_rx) = crate::state::connection_channel();
+        let close_feedback = |rx: &mut tokio::sync::mpsc::Receiver<Message>| {
+            drained!(rx, ServerMessage::CloseGuess { streak, message, .. } => (streak, message))
+        };
+
+        handle_chat(&state, &room_code, "apples", ids[1], "guesser", &tx).await;
+        assert_eq!(close_feedback(&mut own_rx), vec![(1, "You're close!".to_string())]);
+        handle_chat(&state, &room_code, "aple", ids[1], "guesser", &tx).await;
+        assert_eq!(close_feedback(&mut own_rx), vec![(2, "Very close!".to_string())]);
+
+        // A far-off guess ends the streak
+        handle_chat(&state, &room_code, "banana", ids[1], "guesser", &tx).await;
+        assert!(close_feedback(&mut own_rx).is_empty());
+        assert!(state.get_room(&room_code).unwrap().near_miss_streaks.is_empty());
+        handle_chat(&state, &room_code, "appl", ids[1], "guesser", &tx).await;
+        assert_eq!(close_feedback(&mut own_rx), vec![(1, "You're close!".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_spelled_out_word_is_held_back_but_plain_guess_counts() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 0, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser", "other"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut other_rx = connect(&state, &room_code, ids[2]);
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "a p p l e", ids[1], "guesser", &tx).await;
+        assert!(drain(&mut other_rx).iter().all(|m| !matches!(m, ServerMessage::ChatMessage { .. })));
+        assert!(drain(&mut own_rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+        assert!(!state.get_room(&room_code).unwrap().winners.contains(&ids[1]));
+
+        handle_chat(&state, &room_code, "apple", ids[1], "guesser", &tx).await;
+        assert!(state.get_room(&room_code).unwrap().winners.contains(&ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_spectator_does_not_hold_up_everyone_guessed() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 0, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c", "watcher"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.players.get_mut(&ids[3]).unwrap().state = crate::models::PlayerState::Spectator;
+        });
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "b", &tx).await;
+        assert!(!drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::AllGuessed { .. })));
+
+        handle_chat(&state, &room_code, "apple", ids[2], "c", &tx).await;
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::AllGuessed { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_guess_matches_case_insensitively_and_reveals_stored_casing() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "Apple");
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, " apple", ids[1], "guesser", &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(room.winners.contains(&ids[1]));
+        assert_eq!(room.current_round_guesses[0].word, "Apple");
+        let revealed: Vec<String> = drained!(&mut rx, ServerMessage::CorrectGuess { word, .. } | ServerMessage::AllGuessed { word, .. } => word);
+        assert_eq!(revealed, vec!["Apple".to_string(), "Apple".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_drawer_matching_own_word_neither_scores_nor_ends_round() {
+        let state = AppState::new();
+        let (room_code, ids, mut rx) = round_with_guesser(&state, "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+
+        handle_correct_guess(&state, &room_code, "apple", ids[0], "artist").await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(room.current_round_guesses.is_empty());
+        assert_eq!(room.round_token, round_token);
+        assert_eq!(room.word.as_deref(), Some("apple"));
+        assert!(drain(&mut rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_guess_progress_tracks_each_correct_guess() {
+        let state = AppState::with_config(ServerConfig { guess_cooldown_ms: 0, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "bob", "carol"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "bob", &tx).await;
+        handle_chat(&state, &room_code, "apple", ids[2], "carol", &tx).await;
+
+        let progress: Vec<(Vec<Uuid>, usize)> = drained!(&mut rx, ServerMessage::GuessProgress { guessed, remaining } => (guessed, remaining));
+        assert_eq!(progress, vec![(vec![ids[1]], 1), (vec![ids[1], ids[2]], 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_plain_chat_sends_only_the_chat_message() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["alice", "bob"]);
+        let mut rx = connect(&state, &room_code, ids[1]);
+        let (tx, _tx_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "hello there", ids[0], "alice", &tx).await;
+
+        let messages = drain(&mut rx);
+        assert_eq!(messages.len(), 1, "got {:?}", messages);
+        assert!(matches!(&messages[0], ServerMessage::ChatMessage { message } if message.message == "hello there"));
+        assert_eq!(state.get_room(&room_code).unwrap().chat_messages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spectator_chat_follows_room_setting_and_is_flagged() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["alice", "watcher"]);
+        state.with_room_mut(&room_code, |room| {
+            room.players.get_mut(&ids[1]).unwrap().state = crate::models::PlayerState::Spectator;
+            room.allow_spectator_chat = false;
+        });
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "nice drawing", ids[1], "watcher", &tx).await;
+        assert!(drain(&mut rx).is_empty());
+        assert!(matches!(drain(&mut own_rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        state.with_room_mut(&room_code, |room| room.allow_spectator_chat = true);
+        handle_chat(&state, &room_code, "nice drawing", ids[1], "watcher", &tx).await;
+        assert!(matches!(
+            drain(&mut rx).as_slice(),
+            [ServerMessage::ChatMessage { message }] if message.is_spectator && message.message == "nice drawing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_chat_history_hides_winners_chat_from_non_winners() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "winner", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.winners.push(ids[1]));
+        let (tx, _own_rx) = crate::state::connection_channel();
+        handle_chat(&state, &room_code, "well drawn", ids[1], "winner", &tx).await;
+        handle_chat(&state, &room_code, "is it a fruit", ids[2], "guesser", &tx).await;
+
+        let history_for = |player_id| {
+            let (tx, mut rx) = crate::state::connection_channel();
+            let state = state.clone();
+            let room_code = room_code.clone();
+            async move {
+                handle_request_chat_history(&state, &room_code, player_id, &tx).await;
+                match drain(&mut rx).as_slice() {
+                    [ServerMessage::ChatHistory { messages }] => messages.iter().map(|m| m.message.clone()).collect::<Vec<_>>(),
+                    other => panic!("expected chat history, got {:?}", other),
+                }
+            }
+        };
+        assert_eq!(history_for(ids[1]).await, vec!["well drawn", "is it a fruit"]);
+        assert_eq!(history_for(ids[2]).await, vec!["is it a fruit"]);
+    }
+
+    #[tokio::test]
+    async fn test_muted_player_chat_is_dropped_until_unmuted() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "loud"]);
+        let mut host_rx = connect(&state, &room_code, ids[0]);
+        let (host_tx, _host_own) = crate::state::connection_channel();
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        crate::websocket::rooms::handle_mute_player(&state, &room_code, ids[0], ids[1], true, &host_tx).await;
+        assert!(matches!(
+            drain(&mut host_rx).as_slice(),
+            [ServerMessage::PlayerMuted { player_id, muted: true }] if *player_id == ids[1]
+        ));
+        handle_chat(&state, &room_code, "spam", ids[1], "loud", &tx).await;
+        assert!(drain(&mut host_rx).is_empty());
+        assert!(matches!(drain(&mut own_rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        crate::websocket::rooms::handle_mute_player(&state, &room_code, ids[0], ids[1], false, &host_tx).await;
+        handle_chat(&state, &room_code, "sorry", ids[1], "loud", &tx).await;
+        let messages = drain(&mut host_rx);
+        assert!(messages.iter().any(|m| matches!(m, ServerMessage::ChatMessage { message } if message.message == "sorry")));
+    }
+
+    #[tokio::test]
+    async fn test_only_host_can_mute_and_muted_player_can_still_guess() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "artist", "guesser"]);
+        start_round(&state, &room_code, ids[1], "apple");
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        crate::websocket::rooms::handle_mute_player(&state, &room_code, ids[1], ids[2], true, &tx).await;
+        assert!(state.get_room(&room_code).unwrap().muted.is_empty());
+        assert!(matches!(drain(&mut own_rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        crate::websocket::rooms::handle_mute_player(&state, &room_code, ids[0], ids[2], true, &tx).await;
+        handle_chat(&state, &room_code, "apple", ids[2], "guesser", &tx).await;
+        assert!(state.get_room(&room_code).unwrap().winners.contains(&ids[2]));
+    }
+
+    #[tokio::test]
+    async fn test_first_blood_fires_once_for_the_earliest_guesser() {
+        let state = AppState::with_config(ServerConfig { all_guessed_advance_ms: 60_000, ..ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["artist", "quick", "slow"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_chat(&state, &room_code, "apple", ids[1], "quick", &tx).await;
+        handle_chat(&state, &room_code, "apple", ids[2], "slow", &tx).await;
+
+        let first_bloods: Vec<Uuid> = drained!(&mut rx, ServerMessage::FirstBlood { player } => player.id);
+        assert_eq!(first_bloods, vec![ids[1]]);
+    }
+}
@@ -1,52 +1,98 @@
 use crate::models::{DrawPath, DrawStroke, FrontendDrawPath, FrontendDrawStroke};
-use crate::state::AppState;
-use crate::utils::{convert_color, convert_brush_size};
+use crate::state::{AppState, ConnectionSender};
+use crate::utils::{convert_color, convert_brush_size, normalize_color_hex};
 use axum::extract::ws::Message;
-use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
+/// Clamp a point onto the room's canvas; None for coordinates that aren't real numbers
+fn clamp_to_canvas(x: f32, y: f32, width: u32, height: u32) -> Option<(f32, f32)> {
+    if !x.is_finite() || !y.is_finite() {
+        return None;
+    }
+    Some((x.clamp(0.0, width as f32), y.clamp(0.0, height as f32)))
+}
+
 /// Handle drawing update messages (complete paths)
 pub async fn handle_draw_update(
     state: &AppState,
     room_code: &str,
+    sender: Option<Uuid>,
     path: &FrontendDrawPath,
-    _tx: &UnboundedSender<Message>,
+    tx: &ConnectionSender,
 ) {
+    // The path's color and brush come from its first stroke, so an empty path has nothing to draw
+    let rejection = if path.strokes.is_empty() {
+        Some("Path has no strokes".to_string())
+    } else if path.strokes.len() > state.config.max_path_strokes {
+        Some(format!("Path has too many strokes (max {})", state.config.max_path_strokes))
+    } else {
+        None
+    };
+    if let Some(message) = rejection {
+        println!("Rejected drawing update in room {}: {} strokes", room_code, path.strokes.len());
+        let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+        return;
+    }
+
     // Get the room
-    if let Some(mut room) = state.get_room(room_code) {
-        // TODO: Get the actual player ID from the WebSocket connection
-        // For now, we'll assume the current drawer is the one sending
-        if let Some(_current_drawer) = room.current_drawer {
+    if let Some(room) = state.get_room(room_code) {
+        if let Err(message) = require_drawer(&room, sender) {
+            send_drawing_error(tx, message);
+            return;
+        }
+        if let Some(current_drawer) = room.current_drawer {
             // Convert frontend path to backend path
             // IMPORTANT: Preserve the frontend ID to prevent duplicate processing
             let backend_path = DrawPath {
                 id: Uuid::parse_str(&path.id).unwrap_or_else(|_| Uuid::new_v4()),
-                player_id: _current_drawer,
+                player_id: current_drawer,
                 color: convert_color(&path.strokes[0].color),
-                color_hex: path.strokes[0].color.clone(), // Keep original hex color
+                color_hex: normalize_color_hex(&path.strokes[0].color),
                 brush_size: convert_brush_size(path.strokes[0].brush_size),
-                strokes: path.strokes.iter().map(|stroke| DrawStroke {
-                    x: stroke.x,
-                    y: stroke.y,
-                    timestamp: chrono::Utc::now().timestamp() as u64,
-                    color_hex: stroke.color.clone(),
-                    alpha: if stroke.alpha == 0.0 { 1.0 } else { stroke.alpha },
-                    is_eraser: stroke.is_eraser,
-                    brush_px: stroke.brush_size,
-                    brush_size: convert_brush_size(stroke.brush_size),
+                strokes: path.strokes.iter().filter_map(|stroke| {
+                    // Points that aren't real numbers are dropped, the rest are clamped onto the canvas
+                    let (x, y) = clamp_to_canvas(stroke.x, stroke.y, room.canvas_width, room.canvas_height)?;
+                    Some(DrawStroke {
+                        x,
+                        y,
+                        timestamp: chrono::Utc::now().timestamp() as u64,
+                        color_hex: normalize_color_hex(&stroke.color),
+                        alpha: if stroke.alpha == 0.0 { 1.0 } else { stroke.alpha },
+                        is_eraser: stroke.is_eraser,
+                        brush_px: stroke.brush_size,
+                        brush_size: convert_brush_size(stroke.brush_size),
+                    })
                 }).collect(),
                 created_at: chrono::Utc::now(),
             };
-            
-            // Add path to room's drawing_paths
-            room.drawing_paths.push(backend_path.clone());
-            
-            // Update the room in state
-            if let Err(e) = state.update_room(room_code, room) {
-                println!("Failed to update room {}: {}", room_code, e);
+            // A path whose points were all invalid is as empty as one sent without any
+            if backend_path.strokes.is_empty() {
+                println!("Rejected drawing update in room {}: no valid points", room_code);
+                send_drawing_error(tx, "Path has no valid points".to_string());
                 return;
             }
             
+            // Add path to room's drawing_paths under the room lock, as long as the turn hasn't moved on
+            let stored = state.with_room_mut(room_code, |room| {
+                require_drawer(room, sender)?;
+                room.drawing_paths.push(backend_path.clone());
+                Ok(())
+            });
+            match stored {
+                Some(Ok(())) => {}
+                Some(Err(message)) => {
+                    send_drawing_error(tx, message);
+                    return;
+                }
+                None => {
+                    println!("Failed to update room {}: room not found", room_code);
+                    return;
+                }
+            }
+            
             // Broadcast drawing update to all players in the room
             let draw_msg = crate::models::ServerMessage::DrawUpdate {
                 room_code: room_code.to_string(),
@@ -65,24 +111,153 @@ pub async fn handle_draw_update(
     }
 }
 
+/// Only the sender bound to this socket may edit the canvas, and only while they're the
+/// room's drawer; the room code comes from the client, so it proves nothing on its own
+fn require_drawer(room: &crate::models::Room, sender: Option<Uuid>) -> Result<(), String> {
+    match sender {
+        Some(id) if room.current_drawer == Some(id) => Ok(()),
+        _ => Err("Only the current drawer can change the canvas".to_string()),
+    }
+}
+
+/// Tell the sender their drawing change was refused
+fn send_drawing_error(tx: &ConnectionSender, message: String) {
+    let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+    if let Ok(json) = serde_json::to_string(&error_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
+/// Handle undo: remove the most recent path, within the room's per-round undo limit
+pub async fn handle_undo(
+    state: &AppState,
+    room_code: &str,
+    sender: Option<Uuid>,
+    tx: &ConnectionSender,
+) {
+    let result = state.with_room_mut(room_code, |room| {
+        require_drawer(room, sender)?;
+        if let Some(max_undo) = room.max_undo
+            && room.undos_used >= max_undo {
+            return Err(format!("No undos left this round (limit {})", max_undo));
+        }
+        let Some(path) = room.drawing_paths.pop() else {
+            return Ok(None);
+        };
+        room.undos_used += 1;
+        Ok(Some(path.id))
+    });
+
+    match result {
+        Some(Ok(Some(path_id))) => {
+            let undo_msg = crate::models::ServerMessage::PathUndone {
+                room_code: room_code.to_string(),
+                path_id,
+            };
+            if let Ok(json) = serde_json::to_string(&undo_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+        Some(Ok(None)) => println!("Nothing to undo in room {}", room_code),
+        Some(Err(message)) => send_drawing_error(tx, message),
+        None => println!("Room {} not found for undo", room_code),
+    }
+}
+
+/// Handle clear canvas: drop every path drawn this round. Doesn't use up undos.
+pub async fn handle_clear_canvas(
+    state: &AppState,
+    room_code: &str,
+    sender: Option<Uuid>,
+    tx: &ConnectionSender,
+) {
+    let result = state.with_room_mut(room_code, |room| {
+        require_drawer(room, sender)?;
+        room.drawing_paths.clear();
+        Ok(())
+    });
+
+    match result {
+        Some(Ok(())) => {
+            let cleared_msg = crate::models::ServerMessage::CanvasCleared { room_code: room_code.to_string() };
+            if let Ok(json) = serde_json::to_string(&cleared_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+        Some(Err(message)) => send_drawing_error(tx, message),
+        None => println!("Room {} not found for clear canvas", room_code),
+    }
+}
+
+// Upper bound on strokes carried by one CanvasSyncChunk
+const CANVAS_SYNC_CHUNK_STROKES: usize = 500;
+
+/// Split paths into consecutive chunks of at most `max_strokes` strokes; a path larger
+/// than the limit is never split and travels in a chunk of its own
+fn chunk_paths(paths: &[DrawPath], max_strokes: usize) -> Vec<Vec<DrawPath>> {
+    let mut chunks: Vec<Vec<DrawPath>> = Vec::new();
+    let mut current: Vec<DrawPath> = Vec::new();
+    let mut current_strokes = 0;
+    for path in paths {
+        if !current.is_empty() && current_strokes + path.strokes.len() > max_strokes {
+            chunks.push(std::mem::take(&mut current));
+            current_strokes = 0;
+        }
+        current_strokes += path.strokes.len();
+        current.push(path.clone());
+    }
+    // Always send at least one chunk so the client knows the sync is complete
+    if !current.is_empty() || chunks.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Send the room's current canvas to one client as a series of bounded chunks
+pub fn send_canvas_sync(state: &AppState, room_code: &str, tx: &ConnectionSender) {
+    let Some(room) = state.get_room(room_code) else {
+        return;
+    };
+    let chunks = chunk_paths(&room.drawing_paths, CANVAS_SYNC_CHUNK_STROKES);
+    let total = chunks.len() as u32;
+    for (index, paths) in chunks.into_iter().enumerate() {
+        let chunk_msg = crate::models::ServerMessage::CanvasSyncChunk {
+            index: index as u32,
+            total,
+            paths,
+        };
+        if let Ok(json) = serde_json::to_string(&chunk_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+    }
+}
+
 /// Handle live drawing stroke messages
 pub async fn handle_draw_stroke(
     state: &AppState,
     room_code: &str,
+    sender: Option<Uuid>,
     stroke: &FrontendDrawStroke,
-    _tx: &UnboundedSender<Message>,
+    tx: &ConnectionSender,
 ) {
     // Get the room
     if let Some(room) = state.get_room(room_code) {
-        // TODO: Get the actual player ID from the WebSocket connection
-        // For now, we'll assume the current drawer is the one sending
-        if let Some(_current_drawer) = room.current_drawer {
+        if let Err(message) = require_drawer(&room, sender) {
+            send_drawing_error(tx, message);
+            return;
+        }
+        if room.current_drawer.is_some() {
+            let Some((x, y)) = clamp_to_canvas(stroke.x, stroke.y, room.canvas_width, room.canvas_height) else {
+                println!("Dropping live stroke with invalid coordinates in room {}", room_code);
+                return;
+            };
+
             // Convert frontend stroke to backend stroke
             let backend_stroke = DrawStroke {
-                x: stroke.x,
-                y: stroke.y,
+                x,
+                y,
                 timestamp: chrono::Utc::now().timestamp() as u64,
-                color_hex: stroke.color.clone(),
+                color_hex: normalize_color_hex(&stroke.color),
                 alpha: if stroke.alpha == 0.0 { 1.0 } else { stroke.alpha },
                 is_eraser: stroke.is_eraser,
                 brush_px: stroke.brush_size,
@@ -106,3 +281,181 @@ pub async fn handle_draw_stroke(
         println!("Room {} not found for live stroke", room_code);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ServerMessage;
+    use crate::test_support::{connect, drain, drained, room_with_players, start_round};
+
+    fn path_in(color: &str) -> FrontendDrawPath {
+        path_at(color, &[(1.0, 2.0)])
+    }
+
+    fn path_at(color: &str, points: &[(f32, f32)]) -> FrontendDrawPath {
+        FrontendDrawPath {
+            id: Uuid::new_v4().to_string(),
+            strokes: points.iter().map(|&(x, y)| FrontendDrawStroke {
+                x,
+                y,
+                color: color.to_string(),
+                brush_size: 4,
+                alpha: 1.0,
+                is_eraser: false,
+                brush_px: 4,
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_path_is_rejected_without_storing() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        let points = vec![(1.0, 1.0); state.config.max_path_strokes + 1];
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &points), &tx).await;
+
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_configured_stroke_cap_applies_per_path() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            max_path_strokes: 5,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &[(1.0, 1.0); 6]), &tx).await;
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { message, .. }] if message.contains("max 5")));
+
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &[(1.0, 1.0); 5]), &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().drawing_paths.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_empty_path_is_rejected_without_storing() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &[]), &tx).await;
+
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        // Nothing is left once non-finite points are dropped
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &[(f32::NAN, 1.0), (2.0, f32::INFINITY)]), &tx).await;
+
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_color_hex_is_stored_lowercase() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, _rx) = crate::state::connection_channel();
+
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_in("#FF0000"), &tx).await;
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_in("#ff0000"), &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let [upper, lower] = &room.drawing_paths[..] else { panic!("expected two paths") };
+        assert_eq!(upper.color_hex, lower.color_hex);
+        assert_eq!(upper.strokes[0].color_hex, "#ff0000");
+    }
+
+    #[tokio::test]
+    async fn test_large_canvas_sync_arrives_in_bounded_chunks() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let (tx, _rx) = crate::state::connection_channel();
+        for _ in 0..1200 {
+            handle_draw_update(&state, &room_code, Some(ids[0]), &path_in("#000000"), &tx).await;
+        }
+
+        let (sync_tx, mut sync_rx) = crate::state::connection_channel();
+        send_canvas_sync(&state, &room_code, &sync_tx);
+
+        let chunks: Vec<(u32, u32, usize)> = drained!(&mut sync_rx, ServerMessage::CanvasSyncChunk { index, total, paths } => (index, total, paths.len()));
+        assert_eq!(chunks, vec![(0, 3, 500), (1, 3, 500), (2, 3, 200)]);
+    }
+
+    #[tokio::test]
+    async fn test_undo_beyond_max_undo_is_rejected() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.max_undo = Some(1));
+        let (tx, mut rx) = crate::state::connection_channel();
+        for _ in 0..3 {
+            handle_draw_update(&state, &room_code, Some(ids[0]), &path_in("#000000"), &tx).await;
+        }
+        drain(&mut rx);
+
+        handle_undo(&state, &room_code, Some(ids[0]), &tx).await;
+        handle_undo(&state, &room_code, Some(ids[0]), &tx).await;
+
+        assert_eq!(state.get_room(&room_code).unwrap().drawing_paths.len(), 2);
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_only_the_rooms_drawer_can_undo_or_clear() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        let (other_room, other_ids) = room_with_players(&state, &["elsewhere"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        start_round(&state, &other_room, other_ids[0], "pear");
+        let (tx, mut rx) = crate::state::connection_channel();
+        for _ in 0..2 {
+            handle_draw_update(&state, &room_code, Some(ids[0]), &path_in("#000000"), &tx).await;
+        }
+        drain(&mut rx);
+
+        let mut guesser_rx = connect(&state, &room_code, ids[1]);
+
+        for sender in [Some(ids[1]), Some(other_ids[0]), None] {
+            handle_undo(&state, &room_code, sender, &tx).await;
+            handle_clear_canvas(&state, &room_code, sender, &tx).await;
+            handle_draw_update(&state, &room_code, sender, &path_in("#ff0000"), &tx).await;
+            handle_draw_stroke(&state, &room_code, sender, &path_in("#ff0000").strokes[0], &tx).await;
+        }
+        assert_eq!(state.get_room(&room_code).unwrap().drawing_paths.len(), 2);
+        let errors = drain(&mut rx).iter().filter(|m| matches!(m, ServerMessage::Error { .. })).count();
+        assert_eq!(errors, 12);
+        assert!(drain(&mut guesser_rx).is_empty());
+
+        handle_clear_canvas(&state, &room_code, Some(ids[0]), &tx).await;
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_strokes_are_validated_against_custom_canvas() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.canvas_width = 400;
+            room.canvas_height = 300;
+        });
+        let (tx, _rx) = crate::state::connection_channel();
+
+        let points = [(100.0, 250.0), (500.0, -20.0), (f32::NAN, 10.0)];
+        handle_draw_update(&state, &room_code, Some(ids[0]), &path_at("#000000", &points), &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let stored: Vec<(f32, f32)> = room.drawing_paths[0].strokes.iter().map(|s| (s.x, s.y)).collect();
+        assert_eq!(stored, vec![(100.0, 250.0), (400.0, 0.0)]);
+    }
+}
@@ -0,0 +1,119 @@
+use axum::extract::ws::Message;
+use futures_util::{Sink, SinkExt};
+use serde::Serialize;
+use tokio::sync::mpsc::Receiver;
+
+/// Wrapper around every outbound ServerMessage so clients can detect gaps,
+/// reorder and drop duplicates
+#[derive(Debug, Serialize)]
+pub struct Envelope {
+    pub seq: u64,
+    pub server_time: chrono::DateTime<chrono::Utc>,
+    pub payload: serde_json::Value,
+}
+
+/// Per-connection sequence counter; seq starts at 1 and strictly increases
+#[derive(Debug, Default)]
+pub struct Sequencer {
+    last_seq: u64,
+}
+
+impl Sequencer {
+    /// Wrap a text message in an envelope; non-text frames pass through untouched
+    pub fn wrap(&mut self, message: Message) -> Message {
+        let Message::Text(text) = message else {
+            return message;
+        };
+        self.last_seq += 1;
+        let payload = serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+        let envelope = Envelope {
+            seq: self.last_seq,
+            server_time: chrono::Utc::now(),
+            payload,
+        };
+        match serde_json::to_string(&envelope) {
+            Ok(json) => Message::Text(json),
+            Err(e) => {
+                println!("Failed to serialize envelope: {}", e);
+                Message::Text(String::new())
+            }
+        }
+    }
+}
+
+/// Forward queued messages for one connection to its socket, numbering each one.
+/// Protocol v1 clients predate envelopes and get the bare messages.
+pub async fn forward_messages<S>(mut rx: Receiver<Message>, mut sink: S, protocol_version: u32)
+where
+    S: Sink<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    let mut sequencer = Sequencer::default();
+    while let Some(message) = rx.recv().await {
+        let message = if protocol_version >= 2 { sequencer.wrap(message) } else { message };
+        if let Err(e) = sink.send(message).await {
+            println!("Failed to send message: {}", e);
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use crate::test_support::{connect, room_with_players};
+
+    #[tokio::test]
+    async fn test_sequence_numbers_strictly_increase_per_connection() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let rx = connect(&state, &room_code, ids[0]);
+
+        for i in 0..5 {
+            let msg = crate::models::ServerMessage::Error { message: format!("broadcast {}", i), severity: crate::models::ErrorSeverity::Warning };
+            state.broadcast_to_room(&room_code, Message::Text(serde_json::to_string(&msg).unwrap()));
+        }
+        // Dropping the connection closes the queue so forwarding finishes
+        state.remove_connection(&ids[0]);
+
+        let (out_tx, mut out_rx) = tokio::sync::mpsc::unbounded_channel::<Message>();
+        let sink = futures_util::sink::unfold(out_tx, |out_tx, msg: Message| async move {
+            out_tx.send(msg).map(|_| out_tx)
+        });
+        forward_messages(rx, std::pin::pin!(sink), crate::websocket::protocol::PROTOCOL_VERSION).await;
+
+        let mut envelopes: Vec<serde_json::Value> = Vec::new();
+        while let Ok(m) = out_rx.try_recv() {
+            envelopes.push(match m {
+                Message::Text(text) => serde_json::from_str(&text).unwrap(),
+                other => panic!("unexpected frame {:?}", other),
+            });
+        }
+        let seqs: Vec<u64> = envelopes.iter().map(|e| e["seq"].as_u64().unwrap()).collect();
+        assert_eq!(seqs, vec![1, 2, 3, 4, 5]);
+        assert_eq!(envelopes[2]["payload"]["message"], "broadcast 2");
+        assert!(envelopes.iter().all(|e| e["server_time"].is_string()));
+    }
+
+    #[tokio::test]
+    async fn test_v1_clients_get_bare_messages() {
+        let (tx, rx) = crate::state::connection_channel();
+        let msg = crate::models::ServerMessage::Error { message: "hello".to_string(), severity: crate::models::ErrorSeverity::Warning };
+        let _ = tx.send(Message::Text(serde_json::to_string(&msg).unwrap()));
+        drop(tx);
+
+        let (out_tx, mut out_rx) = tokio::sync::mpsc::unbounded_channel::<Message>();
+        let sink = futures_util::sink::unfold(out_tx, |out_tx, msg: Message| async move {
+            out_tx.send(msg).map(|_| out_tx)
+        });
+        forward_messages(rx, std::pin::pin!(sink), 1).await;
+
+        let Ok(Message::Text(text)) = out_rx.try_recv() else {
+            panic!("expected a text frame");
+        };
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["type"], "Error");
+        assert!(value.get("seq").is_none());
+    }
+}
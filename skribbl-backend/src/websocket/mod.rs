@@ -2,5 +2,7 @@ pub mod game;
 pub mod drawing;
 pub mod chat;
 pub mod rooms;
+pub mod envelope;
+pub mod protocol;
 
 
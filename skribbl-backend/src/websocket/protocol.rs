@@ -0,0 +1,44 @@
+// WebSocket protocol versions, negotiated once per connection on the /ws upgrade
+//
+// v1: bare ServerMessage JSON per frame (clients from before sequencing)
+// v2: every ServerMessage wrapped in an Envelope with seq/server_time
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// Oldest version still served through the compatibility shim
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Pick the version to speak with a client; clients that don't send one predate versioning
+pub fn negotiate(requested: Option<u32>) -> Result<u32, String> {
+    let version = requested.unwrap_or(MIN_PROTOCOL_VERSION);
+    if !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version) {
+        return Err(format!(
+            "Unsupported protocol version {} (server supports {} to {})",
+            version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION
+        ));
+    }
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ClientMessage;
+
+    #[test]
+    fn test_matching_and_mismatched_versions() {
+        assert_eq!(negotiate(Some(PROTOCOL_VERSION)), Ok(PROTOCOL_VERSION));
+        assert_eq!(negotiate(None), Ok(MIN_PROTOCOL_VERSION));
+        assert!(negotiate(Some(PROTOCOL_VERSION + 1)).is_err());
+        assert!(negotiate(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_unknown_client_fields_are_rejected() {
+        let typo = r#"{"type":"JoinRoom","room_code":"ABC123","usename":"alice"}"#;
+        assert!(serde_json::from_str::<ClientMessage>(typo).is_err());
+        let extra = r#"{"type":"StartGame","room_code":"ABC123","rounds":3}"#;
+        assert!(serde_json::from_str::<ClientMessage>(extra).is_err());
+        let settings = r#"{"type":"UpdateSettings","room_code":"ABC123","max_rounds":3}"#;
+        assert!(serde_json::from_str::<ClientMessage>(settings).is_ok());
+    }
+}
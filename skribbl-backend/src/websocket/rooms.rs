@@ -1,94 +1,242 @@
-use crate::state::AppState;
+use crate::models::{DrawerChangeReason, JoinErrorCode, Player, PlayerState, RoomEventKind, RoomSettingsUpdate, RoundOutcome};
+use crate::state::{AppState, ConnectionSender};
 use axum::extract::ws::Message;
-use tokio::sync::mpsc::UnboundedSender;
+use rand::seq::SliceRandom;
 use uuid::Uuid;
-use tokio::sync::mpsc;
 
+// Largest max_players a host can set, and what /createRoom gives a normal room
+pub(crate) const MAX_PLAYERS_PER_ROOM: u8 = 8;
 
 /// Handle room joining
 pub async fn handle_join_room(
     state: &AppState,
     room_code: &str,
     username: &str,
-    tx: &UnboundedSender<Message>,
+    auth_token: &str,
+    tx: &ConnectionSender,
     current_player_id: &mut Option<Uuid>,
     current_room_code: &mut Option<String>,
 ) {
     println!("handle_join_room called for {} in room {}", username, room_code);
-    
-    // Check if room exists
-    if let Some(room) = state.get_room(room_code) {
-        println!("Room {} found, current players: {}", room_code, room.players.len());
-        
-        // Check if room is full
-        if room.players.len() >= room.max_players as usize {
-            let error_msg = crate::models::ServerMessage::Error {
-                message: "Room is full".to_string(),
-            };
-            if let Ok(json) = serde_json::to_string(&error_msg) {
-                let _ = tx.send(Message::Text(json));
-            }
-            return;
-        }
-        
-        // For WebSocket joins, we need to find the existing player and establish the connection
-        // The REST API already handled username validation and player creation
-        if let Some(existing_player) = room.players.values().find(|p| p.username == username) {
-            println!("Found existing player {} in room, establishing WebSocket connection", username);
-            
-            // Register WebSocket connection for existing player
-            state.add_connection(existing_player.id, room_code.to_string(), tx.clone());
-            
-            println!("Registered WebSocket connection for existing player {}", username);
-            
-            // Update current connection info
-            *current_player_id = Some(existing_player.id);
-            *current_room_code = Some(room_code.to_string());
-            
-            println!("Updated connection info for player {}", username);
-            
-            // Send success message to joining player
-            let success_msg = crate::models::ServerMessage::PlayerJoined {
-                room_code: room_code.to_string(),
-                player: existing_player.clone(),
-            };
-            if let Ok(json) = serde_json::to_string(&success_msg) {
-                let _ = tx.send(Message::Text(json));
-                println!("Sent success message to player {}", username);
-            }
-            
-            // Broadcast to all other players in the room (excluding the joining player)
-            let broadcast_msg = crate::models::ServerMessage::PlayerJoined {
-                room_code: room_code.to_string(),
-                player: existing_player.clone(),
-            };
-            if let Ok(json) = serde_json::to_string(&broadcast_msg) {
-                println!("Broadcasting PlayerJoined message to room {} (excluding joining player)", room_code);
-                state.broadcast_to_room_excluding(room_code, Message::Text(json), existing_player.id);
-                println!("Broadcast completed for room {}", room_code);
-            }
 
-            // After join, send filtered room state to everyone so visibility is correct
-            state.broadcast_room_state_filtered(room_code);
-            
-            println!("Player {} WebSocket connection established in room {}", username, room_code);
+    let Some(room) = state.get_room(room_code) else {
+        send_join_error(tx, JoinErrorCode::RoomNotFound, "Room not found");
+        return;
+    };
+    println!("Room {} found, current players: {}", room_code, room.players.len());
+
+    // The REST join validates the username and creates the player; this only attaches a connection
+    let Some(existing_player) = room.players.values().find(|p| p.username == username).cloned() else {
+        println!("Player {} not registered in room {}", username, room_code);
+        if room.is_full() {
+            send_join_error(tx, JoinErrorCode::RoomFull, "Room is full");
         } else {
-            println!("Player {} not found in room {}, this shouldn't happen", username, room_code);
-            let error_msg = crate::models::ServerMessage::Error {
-                message: "Player not found in room".to_string(),
-            };
-            if let Ok(json) = serde_json::to_string(&error_msg) {
-                let _ = tx.send(Message::Text(json));
-            }
+            send_join_error(tx, JoinErrorCode::NotRegistered, "Join the room through /joinRoom first");
         }
-    } else {
+        return;
+    };
+    // A username is public, so only the token issued at the REST join may claim the player
+    if !state.validate_auth_token(&existing_player.id, auth_token.trim()) {
+        println!("Rejected JoinRoom for {} in room {}: invalid auth token", username, room_code);
+        send_join_error(tx, JoinErrorCode::InvalidToken, "Invalid auth token for this player");
+        return;
+    }
+
+    attach_player(state, room_code, existing_player, tx, current_player_id, current_room_code);
+}
+
+/// Bind a socket that presented a POST /rooms ticket straight to the player it was issued for
+pub async fn handle_join_with_ticket(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+    current_player_id: &mut Option<Uuid>,
+    current_room_code: &mut Option<String>,
+) {
+    let Some(room) = state.get_room(room_code) else {
+        send_join_error(tx, JoinErrorCode::RoomNotFound, "Room not found");
+        return;
+    };
+    let Some(player) = room.players.get(&player_id).cloned() else {
+        send_join_error(tx, JoinErrorCode::NotRegistered, "This ticket's player has left the room");
+        return;
+    };
+    attach_player(state, room_code, player, tx, current_player_id, current_room_code);
+}
+
+/// Attach a connection to a player already registered in the room and announce them
+fn attach_player(
+    state: &AppState,
+    room_code: &str,
+    existing_player: Player,
+    tx: &ConnectionSender,
+    current_player_id: &mut Option<Uuid>,
+    current_room_code: &mut Option<String>,
+) {
+    let username = existing_player.username.clone();
+    // A repeated JoinRoom on the socket that already holds this player (e.g. a double-click)
+    // changes nothing; announcing it again would duplicate the player in everyone's roster
+    let already_attached_here = *current_player_id == Some(existing_player.id)
+        && current_room_code.as_deref() == Some(room_code)
+        && state.connections.contains_key(&existing_player.id);
+    if already_attached_here {
+        println!("Ignoring duplicate JoinRoom from {} in room {}", username, room_code);
+        return;
+    }
+    let attached_elsewhere = *current_player_id != Some(existing_player.id)
+        && state.connections.contains_key(&existing_player.id);
+    if attached_elsewhere {
+        println!("Player {} already has a connection in room {}", username, room_code);
+        send_join_error(tx, JoinErrorCode::AlreadyConnected, "This player is already connected");
+        return;
+    }
+
+    println!("Found existing player {} in room, establishing WebSocket connection", username);
+
+    // Register WebSocket connection for existing player
+    state.add_connection(existing_player.id, room_code.to_string(), tx.clone());
+    // Announce the player as the room now has them, connected, so WS and presence agree
+    let existing_player = state
+        .with_room_mut(room_code, |room| {
+            let player = room.players.get_mut(&existing_player.id)?;
+            player.is_connected = true;
+            player.disconnected_at = None;
+            Some(player.clone())
+        })
+        .flatten()
+        .unwrap_or(existing_player);
+
+    println!("Registered WebSocket connection for existing player {}", username);
+
+    // Update current connection info
+    *current_player_id = Some(existing_player.id);
+    *current_room_code = Some(room_code.to_string());
+
+    println!("Updated connection info for player {}", username);
+    resume_paused_round(state, room_code, existing_player.id);
+
+    // Send success message to joining player
+    let success_msg = crate::models::ServerMessage::PlayerJoined {
+        room_code: room_code.to_string(),
+        player: existing_player.clone(),
+    };
+    if let Ok(json) = serde_json::to_string(&success_msg) {
+        let _ = tx.send(Message::Text(json));
+        println!("Sent success message to player {}", username);
+    }
+
+    // Broadcast to all other players in the room (excluding the joining player)
+    let broadcast_msg = crate::models::ServerMessage::PlayerJoined {
+        room_code: room_code.to_string(),
+        player: existing_player.clone(),
+    };
+    if let Ok(json) = serde_json::to_string(&broadcast_msg) {
+        println!("Broadcasting PlayerJoined message to room {} (excluding joining player)", room_code);
+        state.broadcast_to_room_excluding(room_code, Message::Text(json), existing_player.id);
+        println!("Broadcast completed for room {}", room_code);
+    }
+
+    // After join, send filtered room state to everyone so visibility is correct
+    state.broadcast_room_state_filtered(room_code);
+
+    // Bring the joiner's canvas up to date without one oversized message
+    super::drawing::send_canvas_sync(state, room_code, tx);
+
+    println!("Player {} WebSocket connection established in room {}", username, room_code);
+}
+
+/// Tell a connection why its JoinRoom was refused
+fn send_join_error(tx: &ConnectionSender, error_code: JoinErrorCode, message: &str) {
+    let error_msg = crate::models::ServerMessage::JoinError {
+        error_code,
+        message: message.to_string(),
+    };
+    if let Ok(json) = serde_json::to_string(&error_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
+/// Re-bind a connection to an existing player after a page reload and restore their view
+pub async fn handle_reconnect(
+    state: &AppState,
+    room_code: &str,
+    player_id: &str,
+    reconnect_token: &str,
+    tx: &ConnectionSender,
+    current_player_id: &mut Option<Uuid>,
+    current_room_code: &mut Option<String>,
+) {
+    let player_id = Uuid::parse_str(player_id.trim())
+        .ok()
+        .filter(|id| state.validate_auth_token(id, reconnect_token.trim()));
+    let rebound = player_id.and_then(|id| {
+        state.with_room_mut(room_code, |room| {
+            let player = room.players.get_mut(&id)?;
+            player.is_connected = true;
+            player.disconnected_at = None;
+            Some((player.clone(), room.clone()))
+        }).flatten()
+    });
+    let Some((player, room)) = rebound else {
         let error_msg = crate::models::ServerMessage::Error {
-            message: "Room not found".to_string(),
+            message: "Could not reconnect: unknown player or invalid token".to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
         };
         if let Ok(json) = serde_json::to_string(&error_msg) {
             let _ = tx.send(Message::Text(json));
         }
+        return;
+    };
+
+    // This socket was bound to someone else: they go through the normal disconnect first
+    if let Some(previous_id) = current_player_id.filter(|id| *id != player.id) {
+        handle_disconnect(state, previous_id, current_room_code.as_deref()).await;
+    }
+    // A reload can beat the old tab's socket closing; the newest connection wins
+    let replaced = state.connections.get(&player.id).map(|conn| conn.sender.clone());
+    if let Some(old_sender) = replaced.filter(|old| !old.same_channel(tx)) {
+        println!("Player {} reconnected to room {}, closing their previous connection", player.username, room_code);
+        let error_msg = crate::models::ServerMessage::Error {
+            message: "You connected from somewhere else".to_string(),
+            severity: crate::models::ErrorSeverity::Fatal,
+        };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = old_sender.send(Message::Text(json));
+        }
+        old_sender.close();
+    }
+
+    state.add_connection(player.id, room_code.to_string(), tx.clone());
+    *current_player_id = Some(player.id);
+    *current_room_code = Some(room_code.to_string());
+    // A drawer coming back restarts a paused clock; sync them against the resumed round
+    resume_paused_round(state, room_code, player.id);
+    let room = state.get_room(room_code).unwrap_or(room);
+
+    // Nothing to restore for a winner: `winners` holds the stable player id and is never
+    // touched by a disconnect, and every filtered send reads it from the live room
+    let now = chrono::Utc::now();
+    let picking_word = room.current_drawer == Some(player.id) && room.word.is_none();
+    let sync_msg = crate::models::ServerMessage::FullSync {
+        room: AppState::room_view_for(&room, &player.id),
+        word_choices: if picking_word { room.pending_word_choices.clone() } else { Vec::new() },
+        round_time_remaining_secs: room
+            .round_end_time
+            .map(|end| end.signed_duration_since(now).num_seconds().max(0) as u32),
+    };
+    if let Ok(json) = serde_json::to_string(&sync_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+
+    let joined_msg = crate::models::ServerMessage::PlayerJoined {
+        room_code: room_code.to_string(),
+        player: player.clone(),
+    };
+    if let Ok(json) = serde_json::to_string(&joined_msg) {
+        state.broadcast_to_room_excluding(room_code, Message::Text(json), player.id);
     }
+    println!("Player {} reconnected to room {}", player.username, room_code);
 }
 
 /// Handle room leaving
@@ -96,7 +244,7 @@ pub async fn handle_leave_room(
     state: &AppState,
     room_code: &str,
     player_id: &str,
-    tx: &UnboundedSender<Message>,
+    tx: &ConnectionSender,
     current_player_id: &mut Option<Uuid>,
     current_room_code: &mut Option<String>,
 ) {
@@ -113,6 +261,7 @@ pub async fn handle_leave_room(
             println!("Failed to parse player_id: {}", e);
             let error_msg = crate::models::ServerMessage::Error {
                 message: "Invalid player ID format".to_string(),
+                severity: crate::models::ErrorSeverity::Warning,
             };
             if let Ok(json) = serde_json::to_string(&error_msg) {
                 let _ = tx.send(Message::Text(json));
@@ -121,7 +270,25 @@ pub async fn handle_leave_room(
         }
     };
     
+    // A connection may only remove the player it is bound to
+    if *current_player_id != Some(player_id_uuid) {
+        println!("Rejected leave for player {}: connection is bound to {:?}", player_id_uuid, current_player_id);
+        let error_msg = crate::models::ServerMessage::Error {
+            message: "Cannot leave on behalf of another player".to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
+        };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+        return;
+    }
+    
     println!("Calling state.remove_player_from_room for room {} and player {}", room_code, player_id_uuid);
+    let was_host = state
+        .get_room(room_code)
+        .map(|room| room.host_id == player_id_uuid)
+        .unwrap_or(false);
+    let was_drawing = state.get_room(room_code).is_some_and(|room| holds_turn(&room, player_id_uuid));
     
     // Remove player from room
     match state.remove_player_from_room(room_code, &player_id_uuid) {
@@ -145,47 +312,13 @@ pub async fn handle_leave_room(
                 let _ = tx.send(Message::Text(json));
             }
             
-            // Check if this was the host and transfer ownership if needed
+            // Hand the room to someone else, or make sure an empty room is gone
+            if was_host {
+                println!("Host {} is leaving, transferring ownership", player.username);
+                reassign_host_after_leave(state, room_code);
+            }
+
             if !room_will_be_empty {
-                // Check if this was the host BEFORE removing the player
-                let was_host = if let Some(room) = state.get_room(room_code) {
-                    room.host_id == player_id_uuid
-                } else {
-                    false
-                };
-                
-                if was_host {
-                    // This was the host, transfer ownership
-                    println!("Host {} is leaving, transferring ownership", player.username);
-                    if let Ok(new_host_id) = state.transfer_host_ownership(room_code) {
-                        // Get the new host info AFTER the transfer
-                        if let Some(new_host) = state.get_player(&new_host_id) {
-                            println!("Host ownership transferred to {}", new_host.username);
-                            
-                            // CRITICAL: Update the room state to reflect the new host BEFORE broadcasting
-                            if let Some(mut room) = state.get_room(room_code) {
-                                room.host_id = new_host_id;
-                                if let Err(e) = state.update_room(room_code, room) {
-                                    println!("Failed to update room with new host: {}", e);
-                                }
-                            }
-                            
-                            // Broadcast host change to remaining players
-                            let host_change_msg = crate::models::ServerMessage::HostChanged {
-                                new_host: new_host.clone(),
-                            };
-                            if let Ok(json) = serde_json::to_string(&host_change_msg) {
-                                println!("Broadcasting HostChanged message to remaining players");
-                                state.broadcast_to_room(room_code, Message::Text(json));
-                            }
-                        } else {
-                            println!("Failed to get new host player info");
-                        }
-                    } else {
-                        println!("Failed to transfer host ownership");
-                    }
-                }
-                
                 // Broadcast PlayerLeft message to remaining players
                 let broadcast_msg = crate::models::ServerMessage::PlayerLeft {
                     room_code: room_code.to_string(),
@@ -195,6 +328,8 @@ pub async fn handle_leave_room(
                     println!("Broadcasting PlayerLeft message to remaining players in room {}", room_code);
                     state.broadcast_to_room(room_code, Message::Text(json));
                 }
+                broadcast_draw_order(state, room_code);
+                pass_turn_from_departed_drawer(state, room_code, was_drawing, DrawerChangeReason::Skip);
             } else {
                 println!("Room {} will be empty after player {} leaves, no broadcast needed", room_code, player_id);
             }
@@ -205,6 +340,7 @@ pub async fn handle_leave_room(
             println!("remove_player_from_room failed: {}", e);
             let error_msg = crate::models::ServerMessage::Error {
                 message: format!("Failed to leave room: {}", e),
+                severity: crate::models::ErrorSeverity::Warning,
             };
             if let Ok(json) = serde_json::to_string(&error_msg) {
                 let _ = tx.send(Message::Text(json));
@@ -213,362 +349,2776 @@ pub async fn handle_leave_room(
     }
 }
 
-/// Handle game start
-pub async fn handle_start_game(
+/// Whether the player holds the turn in a game in progress, drawing or still choosing a word
+pub(crate) fn holds_turn(room: &crate::models::Room, player_id: Uuid) -> bool {
+    room.game_state == crate::models::GameState::Playing && room.current_drawer == Some(player_id)
+}
+
+/// A drawer who has left can't finish their turn, and while they were choosing there is no
+/// round timer to end it either: end the round now and hand the turn on
+pub(crate) fn pass_turn_from_departed_drawer(state: &AppState, room_code: &str, was_drawing: bool, reason: DrawerChangeReason) {
+    if was_drawing && let Some(outcome) = state.end_round(room_code) {
+        broadcast_round_outcome(state, room_code, &outcome, reason);
+    }
+}
+
+/// Tell the room this game's turn order. Only sent mid-game, and never in blind-artist
+/// rooms where it would give away who is drawing. A drawer who left mid-turn is already dropped.
+pub(crate) fn broadcast_draw_order(state: &AppState, room_code: &str) {
+    let Some(room) = state.get_room(room_code) else {
+        return;
+    };
+    if room.game_state != crate::models::GameState::Playing || !room.show_draw_order || !room.reveal_drawer {
+        return;
+    }
+    let order = room.draw_order.iter().copied().filter(|id| room.players.contains_key(id)).collect();
+    let order_msg = crate::models::ServerMessage::DrawOrder { order };
+    if let Ok(json) = serde_json::to_string(&order_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+}
+
+/// After the host has left, hand the room to a remaining player and announce it. If nobody
+/// is left to take over, make sure the empty room is gone and announce nothing.
+pub(crate) fn reassign_host_after_leave(state: &AppState, room_code: &str) {
+    match state.transfer_host_ownership(room_code) {
+        Ok(new_host_id) => {
+            // Look the new host up in the room as it is now, not a copy from before the leave
+            let new_host = state
+                .get_room(room_code)
+                .and_then(|room| room.players.get(&new_host_id).cloned());
+            if let Some(new_host) = new_host {
+                println!("Host ownership of room {} transferred to {}", room_code, new_host.username);
+                let host_change_msg = crate::models::ServerMessage::HostChanged { new_host };
+                if let Ok(json) = serde_json::to_string(&host_change_msg) {
+                    state.broadcast_to_room(room_code, Message::Text(json));
+                }
+            }
+        }
+        Err(e) => {
+            println!("No one can take over room {}: {}", room_code, e);
+            if state.get_room(room_code).is_some_and(|room| room.players.is_empty() && !room.persistent) {
+                state.remove_room(room_code);
+                state.connections.retain(|_, conn| conn.room_code != room_code);
+            }
+        }
+    }
+}
+
+/// Let the current host hand the room to another connected player
+pub async fn handle_transfer_host(
     state: &AppState,
     room_code: &str,
-    tx: &UnboundedSender<Message>,
+    player_id: Uuid,
+    new_host_id: Uuid,
+    tx: &ConnectionSender,
 ) {
-    // Get the room
-    if let Some(mut room) = state.get_room(room_code) {
-        // Check if room has enough players
-        if room.players.len() < 2 {
-            let error_msg = crate::models::ServerMessage::Error {
-                message: "Need at least 2 players to start".to_string(),
-            };
-            if let Ok(json) = serde_json::to_string(&error_msg) {
-                let _ = tx.send(Message::Text(json));
-            }
-            return;
+    let transfer = state.with_room_mut(room_code, |room| {
+        if room.host_id != player_id {
+            return Err("Only the host can transfer host");
         }
-        
-        // Select first drawer (first player in the room)
-        let drawer_id = *room.players.keys().next().unwrap();
-        
-        // Update room state - NO WORD SELECTED YET, wait for player to choose
-        room.game_state = crate::models::GameState::Playing;
-        room.word = None; // No word until player selects one
-        room.current_drawer = Some(drawer_id);
-        room.round_number = 1; // Round within current cycle
-        room.cycle_number = 1; // Current cycle
-        room.round_start_time = None; // No round start time until word is selected
-        room.round_end_time = None; // No round end time until word is selected
-        
-        println!("Game started in room {}: Round {}, Cycle {} of {}, Drawer: {} (Max Cycles: {})", 
-                room_code, room.round_number, room.cycle_number, room.max_rounds,
-                room.players.get(&drawer_id).map(|p| &p.username).unwrap_or(&"Unknown".to_string()),
-                room.max_rounds);
-        
-        // Reset winners list and current round guesses for new round
-        room.winners.clear();
-        room.current_round_guesses.clear();
-        room.drawing_paths.clear();
-        
-        // Add current drawer to winners list (artist is always a winner)
-        room.winners.push(drawer_id);
-        
-        // Update the room in state
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room: {}", e);
-        }
-        
-        // Broadcast game start to all players
-        let game_start_msg = crate::models::ServerMessage::RoundStart {
-            room_code: room_code.to_string(),
-            drawer: room.players.get(&drawer_id).unwrap().clone(),
+        let Some(new_host) = room.players.get(&new_host_id).cloned() else {
+            return Err("That player is not in this room");
         };
-        if let Ok(json) = serde_json::to_string(&game_start_msg) {
-            state.broadcast_to_room(room_code, Message::Text(json));
+        if !new_host.is_connected {
+            return Err("That player is not connected");
         }
+        room.host_id = new_host_id;
+        room.updated_at = chrono::Utc::now();
+        Ok(new_host)
+    });
 
-        // Send filtered room state so non-winners don't see the word or winners chat
-        state.broadcast_room_state_filtered(room_code);
-        
-        println!("Game started in room {} - waiting for player to select word", room_code);
-    } else {
-        let error_msg = crate::models::ServerMessage::Error {
-            message: "Room not found".to_string(),
-        };
-        if let Ok(json) = serde_json::to_string(&error_msg) {
-            let _ = tx.send(Message::Text(json));
+    match transfer {
+        Some(Ok(new_host)) => {
+            println!("Host of room {} handed to {}", room_code, new_host.username);
+            let host_change_msg = crate::models::ServerMessage::HostChanged { new_host };
+            if let Ok(json) = serde_json::to_string(&host_change_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+        Some(Err(reason)) => {
+            let error_msg = crate::models::ServerMessage::Error { message: reason.to_string(), severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
         }
+        None => println!("Room {} not found for host transfer", room_code),
     }
 }
 
-/// Handle round end
-pub async fn handle_end_round(
+/// Mute or unmute a player's chat (host only). A muted player can still guess.
+pub async fn handle_mute_player(
     state: &AppState,
     room_code: &str,
-    _tx: &UnboundedSender<Message>,
+    player_id: Uuid,
+    target_player_id: Uuid,
+    muted: bool,
+    tx: &ConnectionSender,
 ) {
-    println!("handle_end_round called for room: {}", room_code);
-    
-    // Full round end: compute scores, update players, rotate drawer, reset round state, and broadcast next round
-    if let Some(room) = state.get_room(room_code) {
-        println!("Room found, proceeding with round end logic");
-        // Calculate scores using the scoring system
-        let potential_guessers = room.players.len().saturating_sub(1);
-        let artist_streak = room
-            .players
-            .get(&room.current_drawer.unwrap_or_default())
-            .map(|p| p.artist_streak)
-            .unwrap_or(0);
-
-        let scores = crate::scoring::calculate_round_scores(
-            room.round_number,
-            &room.word.clone().unwrap_or_default(),
-            room.round_duration,
-            room.current_round_guesses.clone(),
-            potential_guessers as u32,
-            artist_streak,
-        );
-
-        // Broadcast round scores
-        let round_scores_msg = crate::models::ServerMessage::RoundScores { scores: scores.clone() };
-        if let Ok(json) = serde_json::to_string(&round_scores_msg) {
-            state.broadcast_to_room(room_code, Message::Text(json));
+    let result = state.with_room_mut(room_code, |room| {
+        if room.host_id != player_id {
+            return Err("Only the host can mute players");
         }
+        if target_player_id == player_id {
+            return Err("You can't mute yourself");
+        }
+        if !room.players.contains_key(&target_player_id) {
+            return Err("That player is not in this room");
+        }
+        if muted {
+            room.muted.insert(target_player_id);
+        } else {
+            room.muted.remove(&target_player_id);
+        }
+        Ok(())
+    });
 
-        // Update player scores and artist streaks
-        super::chat::update_player_scores(state, room_code, &scores).await;
-
-        // Rotate drawer and reset round state for next round
-        println!("About to rotate drawer and update cycle logic");
-        if let Some(mut r2) = state.get_room(room_code) {
-            println!("Got room for cycle logic, proceeding with drawer rotation");
-            // Determine ordered players by joined_at
-            let mut ordered: Vec<_> = r2.players.values().cloned().collect();
-            ordered.sort_by(|a, b| a.joined_at.cmp(&b.joined_at));
-            
-            // Safety check: ensure we have players
-            if ordered.is_empty() {
-                println!("ERROR: No players in room {} during round end", room_code);
-                return;
-            }
-            
-            let current = r2.current_drawer;
-            let next_drawer = if let Some(cur) = current {
-                let cur_idx = ordered.iter().position(|p| p.id == cur).unwrap_or(0);
-                let next_idx = (cur_idx + 1) % ordered.len();
-                ordered[next_idx].id
-            } else {
-                ordered.first().map(|p| p.id).unwrap_or_else(uuid::Uuid::nil)
-            };
-
-            // Check if we're starting a new cycle (back to first player)
-            let is_new_cycle = if let Some(cur) = current {
-                let cur_idx = ordered.iter().position(|p| p.id == cur).unwrap_or(0);
-                let next_idx = (cur_idx + 1) % ordered.len();
-                let will_be_new_cycle = next_idx == 0; // If next drawer is first player, it's a new cycle
-                println!("Cycle check: current_idx={}, next_idx={}, players_total={}, will_be_new_cycle={}", 
-                        cur_idx, next_idx, ordered.len(), will_be_new_cycle);
-                will_be_new_cycle
-            } else {
-                false
-            };
-
-            println!("Before update - Round: {}, Cycle: {}, Max Cycles: {}", 
-                    r2.round_number, r2.cycle_number, r2.max_rounds);
-
-            // Increment round number and cycle number if needed
-            if is_new_cycle {
-                r2.cycle_number = r2.cycle_number.saturating_add(1);
-                r2.round_number = 1; // Reset to 1 for new cycle
-                println!("New cycle started! Cycle {} of {} (max cycles)", r2.cycle_number, r2.max_rounds);
-            } else {
-                r2.round_number = r2.round_number.saturating_add(1); // Increment round within cycle
-            }
-            
-            println!("After update - Round: {}, Cycle: {}, Max Cycles: {}", 
-                    r2.round_number, r2.cycle_number, r2.max_rounds);
-            
-            // CRITICAL FIX: Additional cycle progression logic
-            // Ensure cycle increments every N rounds where N = number of players
-            let players_count = ordered.len() as u32;
-            if r2.round_number > players_count {
-                // We've exceeded the number of players in this cycle, force a new cycle
-                r2.cycle_number = r2.cycle_number.saturating_add(1);
-                r2.round_number = 1; // Reset to 1 for new cycle
-                println!("Force new cycle! Round {} > {} players, Cycle {} of {} (max cycles)", 
-                        r2.round_number, players_count, r2.cycle_number, r2.max_rounds);
-            }
-            
-            // Enhanced debugging: Log the complete state after all updates
-            println!("Final state after cycle logic:");
-            println!("   - Players count: {}", players_count);
-            println!("   - Round number: {}", r2.round_number);
-            println!("   - Cycle number: {}", r2.cycle_number);
-            println!("   - Max cycles: {}", r2.max_rounds);
-            println!("   - Is new cycle: {}", is_new_cycle);
-            
-            // Get current drawer name safely
-            let unknown_str = "Unknown".to_string();
-            let none_str = "None".to_string();
-            
-            let current_drawer_name = if let Some(cur_id) = current {
-                r2.players.get(&cur_id).map(|p| &p.username).unwrap_or(&unknown_str)
-            } else {
-                &none_str
-            };
-            println!("   - Current drawer: {}", current_drawer_name);
-            
-            // Get next drawer name safely
-            let next_drawer_name = if let Some(idx) = ordered.iter().position(|p| p.id == next_drawer) {
-                ordered.get(idx).map(|p| &p.username).unwrap_or(&unknown_str)
-            } else {
-                &unknown_str
-            };
-            println!("   - Next drawer: {}", next_drawer_name);
-            
-            // Log round completion
-            println!(
-                "Round {} complete. Current drawer: {}, Next drawer: {}, Cycle: {} of {}",
-                r2.round_number, current_drawer_name, next_drawer_name, r2.cycle_number, r2.max_rounds
-            );
-            
-            r2.current_drawer = Some(next_drawer);
-            r2.word = None;
-            r2.round_start_time = None;
-            r2.round_end_time = None;
-            r2.current_round_guesses.clear();
-            r2.drawing_paths.clear();
-            r2.winners.clear();
-            r2.winners.push(next_drawer); // artist is always a winner
-
-            let _ = state.update_room(room_code, r2.clone());
-
-            // Check if game should end (max cycles reached)
-            if r2.cycle_number > r2.max_rounds {
-                println!("Game ending: Cycle {} > Max Cycles {} - Game Over!", r2.cycle_number, r2.max_rounds);
-                // Game over - broadcast final scores
-                r2.game_state = crate::models::GameState::Finished;
-                if let Err(e) = state.update_room(room_code, r2.clone()) {
-                    println!("Failed to update room to finished state: {}", e);
-                }
-                
-                let game_end_msg = crate::models::ServerMessage::GameEnded {
-                    final_scores: r2.players.iter().map(|(id, p)| (id.to_string(), p.score)).collect(),
-                };
-                if let Ok(json) = serde_json::to_string(&game_end_msg) {
-                    state.broadcast_to_room(room_code, Message::Text(json));
-                }
-                return; // Don't start next round
+    match result {
+        Some(Ok(())) => {
+            let muted_msg = crate::models::ServerMessage::PlayerMuted { player_id: target_player_id, muted };
+            if let Ok(json) = serde_json::to_string(&muted_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
             }
-
-            // Announce next drawer
-            if let Some(drawer_player) = r2.players.get(&next_drawer) {
-                let next_msg = crate::models::ServerMessage::RoundStart {
-                    room_code: room_code.to_string(),
-                    drawer: drawer_player.clone(),
-                };
-                if let Ok(json) = serde_json::to_string(&next_msg) {
-                    state.broadcast_to_room(room_code, Message::Text(json));
-                }
+        }
+        Some(Err(reason)) => {
+            let error_msg = crate::models::ServerMessage::Error { message: reason.to_string(), severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
             }
-
-            // Send filtered state so visibility is correct
-            state.broadcast_room_state_filtered(room_code);
         }
+        None => println!("Room {} not found for mute", room_code),
     }
 }
 
-/// Handle word selection
-pub async fn handle_word_selected(
+/// Let the host remove a player from the room. The kicked player gets a Fatal error and
+/// their socket is closed; everyone else sees PlayerKicked.
+pub async fn handle_kick_player(
     state: &AppState,
     room_code: &str,
-    word: &str,
-    _tx: &UnboundedSender<Message>,
+    player_id: Uuid,
+    target_player_id: Uuid,
+    tx: &ConnectionSender,
 ) {
-    // Persist the selected word and update round timings
-    if let Some(mut room) = state.get_room(room_code) {
-        // Check if a word is already selected for this round
-        if room.word.is_some() {
-            println!("Word already selected in room {}, ignoring new selection: {}", room_code, word);
-            return;
+    let checked = state.get_room(room_code).map(|room| {
+        if room.host_id != player_id {
+            return Err("Only the host can kick players");
         }
-        
-        // Check if the game is in playing state
-        if room.game_state != crate::models::GameState::Playing {
-            println!("Game not in playing state in room {}, ignoring word selection: {}", room_code, word);
-            return;
+        if target_player_id == player_id {
+            return Err("You can't kick yourself");
         }
-        
-        // Check if there's a current drawer
-        if room.current_drawer.is_none() {
-            println!("No current drawer in room {}, ignoring word selection: {}", room_code, word);
+        if !room.players.contains_key(&target_player_id) {
+            return Err("That player is not in this room");
+        }
+        Ok(holds_turn(&room, target_player_id))
+    });
+    let was_drawing = match checked {
+        Some(Ok(was_drawing)) => was_drawing,
+        Some(Err(reason)) => {
+            let error_msg = crate::models::ServerMessage::Error {
+                message: reason.to_string(),
+                severity: crate::models::ErrorSeverity::Warning,
+            };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
             return;
         }
-        
-        // Clear any existing word and timers
-        room.word = Some(word.to_string());
-        room.round_start_time = Some(chrono::Utc::now());
-        room.round_end_time = Some(chrono::Utc::now() + chrono::Duration::seconds(room.round_duration as i64));
-        
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room with selected word: {}", e);
+        None => {
+            println!("Room {} not found for kick", room_code);
             return;
         }
-        
-        println!("Word selected in room {}: {} (starting {}s timer)", room_code, word, room.round_duration);
-        
-        // Start backend timer to end round automatically
-        // Note: This timer will be the only active timer for this round
-        let room_code_clone = room_code.to_string();
-        let state_clone = state.clone();
-        let round_duration = room.round_duration;
-        let word_clone = word.to_string(); // Clone the word for the async block
-        let current_drawer_id = room.current_drawer; // Store current drawer ID
-        
-        tokio::spawn(async move {
-            tokio::time::sleep(tokio::time::Duration::from_secs(round_duration as u64)).await;
-            
-            // Check if round is still active before ending
-            if let Some(current_room) = state_clone.get_room(&room_code_clone) {
-                // Only end the round if:
-                // 1. Game is still playing
-                // 2. Current drawer exists and is the same as when the timer started
-                // 3. The word is still the same (no new word was selected)
-                // 4. The drawer hasn't changed (prevents old timers from affecting new rounds)
-                if current_room.game_state == crate::models::GameState::Playing 
-                   && current_room.current_drawer.is_some()
-                   && current_room.current_drawer == current_drawer_id
-                   && current_room.word.as_ref() == Some(&word_clone) {
-                    println!("Backend timer expired for word '{}', ending round in room {}", word_clone, room_code_clone);
-                    let (tx_dummy, _rx) = mpsc::unbounded_channel::<Message>();
-                    handle_end_round(&state_clone, &room_code_clone, &tx_dummy).await;
-                } else {
-                    println!("Backend timer expired but round is no longer active, word changed, or drawer changed - not ending round");
-                }
-            }
-        });
-        
-        // Broadcast filtered room state so all clients sync appropriately
-        state.broadcast_room_state_filtered(room_code);
-    }
-
-    // Do NOT broadcast the word globally; state filtering will reveal it only to winners
-    // Instead, send WordSelected with the word to winners, and an empty word to non-winners
-    let word_msg_winners = crate::models::ServerMessage::WordSelected {
-        word: word.to_string(),
     };
-    if let Ok(json) = serde_json::to_string(&word_msg_winners) {
-        state.broadcast_to_winners(room_code, Message::Text(json));
+
+    // Take the connection out first so the kicked player sees nothing after the error
+    let kicked_sender = state.connections.get(&target_player_id).map(|conn| conn.sender.clone());
+    state.remove_connection(&target_player_id);
+    if let Some(sender) = kicked_sender {
+        let error_msg = crate::models::ServerMessage::Error {
+            message: "You have been removed from the room by the host".to_string(),
+            severity: crate::models::ErrorSeverity::Fatal,
+        };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = sender.send(Message::Text(json));
+        }
+        sender.close();
     }
 
-    // Empty string ensures frontend treats it as hidden but still starts timers
-    let word_msg_non_winners = crate::models::ServerMessage::WordSelected {
-        word: String::new(),
+    let Ok((player, _)) = state.remove_player_from_room(room_code, &target_player_id) else {
+        return;
     };
-    if let Ok(json) = serde_json::to_string(&word_msg_non_winners) {
-        state.broadcast_to_non_winners(room_code, Message::Text(json));
+    println!("Host {} kicked {} from room {}", player_id, player.username, room_code);
+    let kicked_msg = crate::models::ServerMessage::PlayerKicked { room_code: room_code.to_string(), player };
+    if let Ok(json) = serde_json::to_string(&kicked_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
     }
+    state.broadcast_room_state_filtered(room_code);
+    broadcast_draw_order(state, room_code);
+    pass_turn_from_departed_drawer(state, room_code, was_drawing, DrawerChangeReason::Skip);
 }
 
-/// Update room settings (host-only). Currently supports max_rounds (1..=5)
-pub async fn handle_update_settings(
+/// Let the host correct a player's score by a signed delta; it stops at zero and the
+/// change is announced as a ScoreUpdate carrying the delta actually applied
+pub async fn handle_adjust_score(
     state: &AppState,
     room_code: &str,
-    max_rounds: u32,
-    _tx: &UnboundedSender<Message>,
+    player_id: Uuid,
+    target_player_id: Uuid,
+    delta: i64,
+    tx: &ConnectionSender,
 ) {
-    let clamped = max_rounds.clamp(1, 5);
-    if let Some(mut room) = state.get_room(room_code) {
-        room.max_rounds = clamped;
-        if let Err(e) = state.update_room(room_code, room.clone()) {
-            println!("Failed to update room settings: {}", e);
-            return;
+    let result = state.with_room_mut(room_code, |room| {
+        if room.host_id != player_id {
+            return Err("Only the host can adjust scores");
         }
-        // Broadcast full room state so all clients sync
+        let Some(target) = room.players.get_mut(&target_player_id) else {
+            return Err("That player is not in this room");
+        };
+        let old_score = target.score;
+        let points = u32::try_from(delta.unsigned_abs()).unwrap_or(u32::MAX);
+        if delta >= 0 {
+            target.add_score(points);
+        } else {
+            target.deduct_score(points);
+        }
+        Ok((target.score, i64::from(target.score) - i64::from(old_score)))
+    });
+
+    match result {
+        Some(Ok((new_score, applied))) => {
+            println!("Host {} adjusted score of {} in room {} by {} (now {})", player_id, target_player_id, room_code, applied, new_score);
+            state.record_event(room_code, RoomEventKind::ScoreAdjusted { by: player_id, player_id: target_player_id, delta: applied, new_score });
+            let score_msg = crate::models::ServerMessage::ScoreUpdate { player_id: target_player_id, new_score, delta: applied };
+            if let Ok(json) = serde_json::to_string(&score_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+        Some(Err(reason)) => {
+            let error_msg = crate::models::ServerMessage::Error { message: reason.to_string(), severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+        }
+        None => println!("Room {} not found for score adjustment", room_code),
+    }
+}
+
+/// Clean up after a player's socket drops without an explicit leave
+pub async fn handle_disconnect(state: &AppState, player_id: Uuid, room_code: Option<&str>) {
+    state.remove_connection(&player_id);
+    let Some(room_code) = room_code else {
+        return;
+    };
+
+    // Disconnected players keep their seat and score for the grace window but no longer count
+    // as potential guessers or drawers. A round can't continue without its drawer, so move on.
+    let disconnected_at = chrono::Utc::now();
+    let (player_state, was_drawing, was_selecting, was_host) = state
+        .with_room_mut(room_code, |room| {
+            let player_state = room.players.get_mut(&player_id).map(|player| {
+                player.is_connected = false;
+                player.disconnected_at = Some(disconnected_at);
+                player.state.clone()
+            });
+            let was_drawing = room.game_state == crate::models::GameState::Playing && room.current_drawer == Some(player_id);
+            let was_host = room.host_id == player_id && room.players.values().any(|p| p.is_connected);
+            (player_state, was_drawing, was_drawing && room.word.is_none(), was_host)
+        })
+        .unwrap_or((None, false, false, false));
+
+    // They haven't left yet: tell the room they dropped, matching what presence now reports
+    if let Some(player_state) = player_state {
+        let disconnect_msg = crate::models::ServerMessage::PlayerStateChanged {
+            player_id,
+            is_connected: false,
+            state: player_state,
+        };
+        if let Ok(json) = serde_json::to_string(&disconnect_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+    }
+    start_disconnect_grace(state, room_code, player_id, disconnected_at);
+    // Without this the room has no one who can start or configure it until the grace window ends
+    if was_host && state.config.transfer_host_on_disconnect {
+        reassign_host_after_leave(state, room_code);
+    }
+    // With the skip turned off, a drawer still choosing keeps the turn until they return or the grace window ends
+    if was_selecting && !state.config.skip_selecting_drawer_on_disconnect {
+        println!("Drawer {} disconnected while choosing a word in room {}, holding their turn", player_id, room_code);
+        return;
+    }
+    if was_drawing && state.config.pause_for_disconnected_drawer && pause_round_for_drawer(state, room_code, player_id) {
+        return;
+    }
+    if was_drawing {
+        println!("Drawer {} disconnected mid-round in room {}, skipping to next drawer", player_id, room_code);
+        if let Some(outcome) = state.end_round(room_code) {
+            broadcast_round_outcome(state, room_code, &outcome, DrawerChangeReason::Disconnect);
+        }
+    }
+}
+
+/// Freeze the round's clock while its drawer is away. If they don't come back within the
+/// grace window, their removal ends the round and the turn passes on.
+fn pause_round_for_drawer(state: &AppState, room_code: &str, drawer_id: Uuid) -> bool {
+    let now = chrono::Utc::now();
+    let paused = state
+        .with_room_mut(room_code, |room| {
+            if room.current_drawer != Some(drawer_id) || room.word.is_none() || room.paused_remaining_ms.is_some() {
+                return false;
+            }
+            let Some(end) = room.round_end_time.take() else {
+                return false;
+            };
+            room.paused_remaining_ms = Some(end.signed_duration_since(now).num_milliseconds().max(0) as u64);
+            true
+        })
+        .unwrap_or(false);
+    if !paused {
+        return false;
+    }
+    if let Some((_, timer)) = state.round_timers.remove(room_code) {
+        timer.abort();
+    }
+    println!("Drawer {} disconnected mid-round in room {}, pausing the round", drawer_id, room_code);
+    let paused_msg = crate::models::ServerMessage::DrawerDisconnected {
+        drawer_id,
+        grace_secs: state.config.disconnect_grace_secs,
+    };
+    if let Ok(json) = serde_json::to_string(&paused_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+    true
+}
+
+/// Restart a round frozen for this drawer, with the time it had left when they dropped
+fn resume_paused_round(state: &AppState, room_code: &str, drawer_id: Uuid) {
+    let resumed = state
+        .with_room_mut(room_code, |room| {
+            if room.current_drawer != Some(drawer_id) {
+                return None;
+            }
+            let remaining_ms = room.paused_remaining_ms.take()?;
+            room.round_end_time = Some(chrono::Utc::now() + chrono::Duration::milliseconds(remaining_ms as i64));
+            Some((remaining_ms, room.round_token))
+        })
+        .flatten();
+    let Some((remaining_ms, round_token)) = resumed else {
+        return;
+    };
+    println!("Drawer {} is back in room {}, resuming the round with {}ms left", drawer_id, room_code, remaining_ms);
+    let timer_state = state.clone();
+    let timer_room_code = room_code.to_string();
+    let timer = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(remaining_ms)).await;
+        expire_round(&timer_state, &timer_room_code, round_token).await;
+    });
+    state.replace_round_timer(room_code, timer.abort_handle());
+
+    let resumed_msg = crate::models::ServerMessage::DrawerReconnected {
+        drawer_id,
+        round_time_remaining_secs: remaining_ms.div_ceil(1000) as u32,
+    };
+    if let Ok(json) = serde_json::to_string(&resumed_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+}
+
+/// Remove a disconnected player once the grace window passes, unless they came back in the meantime
+fn start_disconnect_grace(state: &AppState, room_code: &str, player_id: Uuid, disconnected_at: chrono::DateTime<chrono::Utc>) {
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(state.config.disconnect_grace_secs)).await;
+        let Some(room) = state.get_room(&room_code) else {
+            return;
+        };
+        let still_gone = room
+            .players
+            .get(&player_id)
+            .is_some_and(|p| p.disconnected_at == Some(disconnected_at));
+        if still_gone {
+            println!("Grace period over for player {} in room {}", player_id, room_code);
+            remove_absent_player(&state, &room_code, player_id);
+        }
+    });
+}
+
+/// Remove a REST-joined player who never opened a WebSocket within the connect deadline
+pub(crate) fn start_connect_deadline(state: &AppState, room_code: &str, player_id: Uuid) {
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(state.config.connect_deadline_secs)).await;
+        // A player who connected and dropped since is handled by the disconnect grace instead
+        let never_connected = state
+            .get_room(&room_code)
+            .and_then(|room| room.players.get(&player_id).map(|p| p.disconnected_at.is_none()))
+            .unwrap_or(false)
+            && !state.connections.contains_key(&player_id);
+        if never_connected {
+            println!("Player {} never connected to room {}", player_id, room_code);
+            remove_absent_player(&state, &room_code, player_id);
+        }
+    });
+}
+
+/// Take a player who is no longer around out of the room and tell everyone left
+fn remove_absent_player(state: &AppState, room_code: &str, player_id: Uuid) {
+    let was_host = state.get_room(room_code).is_some_and(|room| room.host_id == player_id);
+    let was_drawing = state.get_room(room_code).is_some_and(|room| holds_turn(&room, player_id));
+    let Ok((player, room_will_be_empty)) = state.remove_player_from_room(room_code, &player_id) else {
+        return;
+    };
+    println!("Removed absent player {} from room {}", player.username, room_code);
+    if was_host {
+        reassign_host_after_leave(state, room_code);
+    }
+    if !room_will_be_empty {
+        let left_msg = crate::models::ServerMessage::PlayerLeft { room_code: room_code.to_string(), player };
+        if let Ok(json) = serde_json::to_string(&left_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+        state.broadcast_room_state_filtered(room_code);
+        broadcast_draw_order(state, room_code);
+        // Only reachable when a drawer held their turn through the grace window
+        pass_turn_from_departed_drawer(state, room_code, was_drawing, DrawerChangeReason::Disconnect);
+    }
+}
+
+/// Handle game start; only the host can start (or restart) a game, and never one in progress
+pub async fn handle_start_game(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    force: bool, // Skip the ready check
+    tx: &ConnectionSender,
+) {
+    let started = state.with_room_mut(room_code, |room| {
+        if room.host_id != player_id {
+            return Err("Only the host can start the game".to_string());
+        }
+        if room.game_state == crate::models::GameState::Playing {
+            return Err("The game is already in progress".to_string());
+        }
+        // Check if room has enough players (practice rooms are played solo)
+        let min_players = if room.practice { 1 } else { 2 };
+        if room.connected_player_count() < min_players {
+            return Err("Need at least 2 players to start".to_string());
+        }
+        if room.require_ready && !force && !room.all_ready() {
+            return Err("Waiting for every player to be ready".to_string());
+        }
+
+        // Fix the rotation for this game; the earliest connected joiner draws first
+        room.draw_order = room.players_by_join_time();
+        let Some(drawer_id) = room.draw_order.iter().copied().find(|id| room.players[id].is_connected) else {
+            return Err("Need at least 2 players to start".to_string());
+        };
+
+        // Update room state - NO WORD SELECTED YET, wait for player to choose
+        room.game_state = crate::models::GameState::Playing;
+        room.word = None; // No word until player selects one
+        room.current_drawer = Some(drawer_id);
+        room.round_number = 1; // Round within current cycle
+        room.cycle_number = 1; // Current cycle
+        room.rounds_remaining = room.rounds_after_current();
+        room.rounds_played = 0;
+        room.used_words.clear();
+        room.round_start_time = None; // No round start time until word is selected
+        room.round_end_time = None; // No round end time until word is selected
+        room.paused_remaining_ms = None;
+        room.round_token = room.round_token.wrapping_add(1); // Invalidate timers from any previous round
+        room.game_started_at = Some(chrono::Utc::now());
+
+        println!("Game started in room {}: Round {}, Cycle {} of {}, Drawer: {} (Max Cycles: {})", 
+                room_code, room.round_number, room.cycle_number, room.max_rounds,
+                room.players.get(&drawer_id).map(|p| &p.username).unwrap_or(&"Unknown".to_string()),
+                room.max_rounds);
+
+        // Scores and streaks only count within a single game; reset here so a refused
+        // start (e.g. a replay vote with too few players left) keeps the last results
+        for player in room.players.values_mut() {
+            player.score = 0;
+            player.artist_streak = 0;
+            player.guess_streak = 0;
+        }
+
+        // Reset winners list and current round guesses for new round
+        room.winners.clear();
+        room.current_round_guesses.clear();
+        room.drawing_paths.clear();
+        room.undos_used = 0;
+        room.skip_votes.clear();
+        room.hint_purchases.clear();
+        room.winners_chat_sent.clear();
+        room.near_miss_streaks.clear();
+        room.word_choices_rerolled = false;
+        room.sudden_death_players.clear();
+        room.play_again_votes.clear();
+
+        // Add current drawer to winners list (artist is always a winner)
+        room.winners.push(drawer_id);
+        room.word_seed = room.verifiable_words.then(crate::words::new_word_seed);
+        room.word_draws = 0;
+        room.pending_word_choices = crate::words::pick_word_choices_for(room, &[]);
+        Ok((room.clone(), drawer_id))
+    });
+    let (room, drawer_id) = match started {
+        Some(Ok(started)) => started,
+        Some(Err(message)) => {
+            let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+        None => {
+            let error_msg = crate::models::ServerMessage::Error {
+                message: "Room not found".to_string(),
+                severity: crate::models::ErrorSeverity::Warning,
+            };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+    };
+
+    // Broadcast game start to all players
+    broadcast_naming_drawer(state, &room, &room.players[&drawer_id], |drawer| round_start_message(&room, drawer));
+
+    broadcast_draw_order(state, room_code);
+    if let Some(seed) = &room.word_seed {
+        let commitment_msg = crate::models::ServerMessage::SeedCommitment { seed_hash: crate::words::seed_commitment(seed) };
+        if let Ok(json) = serde_json::to_string(&commitment_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+    }
+    send_word_choices(state, drawer_id, &room.pending_word_choices);
+    start_word_select_countdown(state, room_code, room.round_token);
+    state.record_event(room_code, RoomEventKind::GameStarted { drawer_id });
+    match room.max_game_duration_secs {
+        Some(limit) => start_game_clock(state, room_code, limit),
+        None => state.stop_game_clock(room_code),
+    }
+
+    // Send filtered room state so non-winners don't see the word or winners chat
+    state.broadcast_room_state_filtered(room_code);
+
+    println!("Game started in room {} - waiting for player to select word", room_code);
+}
+
+/// Mark a player ready (or not) in the lobby and tell the room
+pub async fn handle_set_ready(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    ready: bool,
+    tx: &ConnectionSender,
+) {
+    let updated = state.with_room_mut(room_code, |room| {
+        if room.game_state == crate::models::GameState::Playing {
+            return false;
+        }
+        match room.players.get_mut(&player_id) {
+            Some(player) => {
+                player.ready = ready;
+                true
+            }
+            None => false,
+        }
+    });
+    if updated != Some(true) {
+        let error_msg = crate::models::ServerMessage::Error {
+            message: "Ready state can only be changed in the lobby".to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
+        };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+        return;
+    }
+
+    let ready_msg = crate::models::ServerMessage::ReadyUpdate { player_id, ready };
+    if let Ok(json) = serde_json::to_string(&ready_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+}
+
+/// Handle a practice-room request to skip the current word; only the drawer may ask
+pub async fn handle_next_word(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let rejection = state.get_room(room_code).map_or(Some("Room not found"), |room| {
+        if !room.practice || room.game_state != crate::models::GameState::Playing {
+            Some("Next word is only available during a practice game")
+        } else if room.current_drawer != Some(player_id) {
+            Some("Only the drawer can skip to the next word")
+        } else {
+            None
+        }
+    });
+    if let Some(reason) = rejection {
+        let error_msg = crate::models::ServerMessage::Error {
+            message: reason.to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
+        };
+        if let Ok(json) = serde_json::to_string(&error_msg) {
+            let _ = tx.send(Message::Text(json));
+        }
+        return;
+    }
+
+    // The solo drawer rotates back to themselves and is offered fresh words
+    if let Some(outcome) = state.end_round(room_code) {
+        broadcast_round_outcome(state, room_code, &outcome, DrawerChangeReason::Skip);
+    }
+}
+
+/// Handle the drawer rerolling their word choices: once per round, before a word is picked,
+/// they get a fresh set that repeats neither the discarded choices nor earlier rounds' words
+pub async fn handle_reroll_words(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let reroll = state.with_room_mut(room_code, |room| {
+        if room.game_state != crate::models::GameState::Playing
+            || room.current_drawer != Some(player_id)
+            || room.word.is_some()
+            || room.pending_word_choices.is_empty() {
+            return Err("Only the drawer can reroll, before picking a word".to_string());
+        }
+        if room.word_choices_rerolled {
+            return Err("Word choices can only be rerolled once per round".to_string());
+        }
+        let mut excluded = room.pending_word_choices.clone();
+        excluded.extend(room.gallery.iter().map(|entry| entry.word.clone()));
+        let fresh = crate::words::pick_word_choices_for(room, &excluded);
+        if fresh.len() < crate::words::WORD_CHOICE_COUNT {
+            return Err("No other words are left to offer".to_string());
+        }
+        room.pending_word_choices = fresh.clone();
+        room.word_choices_rerolled = true;
+        Ok(fresh)
+    });
+    match reroll {
+        Some(Ok(words)) => send_word_choices(state, player_id, &words),
+        Some(Err(message)) => {
+            let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+        }
+        None => {}
+    }
+}
+
+/// Handle a vote to play again after the game finished. The host's vote or a strict majority
+/// of connected players restarts the game from zero scores with the same room and settings.
+pub async fn handle_play_again(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let vote = state.with_room_mut(room_code, |room| {
+        if room.game_state != crate::models::GameState::Finished {
+            return Err("The game hasn't finished".to_string());
+        }
+        if room.players.get(&player_id).is_none_or(|p| p.state == PlayerState::Spectator) {
+            return Err("Only players can vote to play again".to_string());
+        }
+        if !room.play_again_votes.contains(&player_id) {
+            room.play_again_votes.push(player_id);
+        }
+        let needed = room.connected_player_count() / 2 + 1;
+        let restart = player_id == room.host_id || room.play_again_votes.len() >= needed;
+        Ok((restart, room.host_id, room.play_again_votes.clone(), needed))
+    });
+    let (restart, host_id, votes, needed) = match vote {
+        Some(Ok(vote)) => vote,
+        Some(Err(message)) => {
+            let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+        None => return,
+    };
+    let update_msg = crate::models::ServerMessage::PlayAgainUpdate { votes, needed };
+    if let Ok(json) = serde_json::to_string(&update_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+    if restart {
+        // A successful vote restarts on the host's behalf and stands in for readying up
+        handle_start_game(state, room_code, host_id, true, tx).await;
+    }
+}
+
+/// Send the current standings to a player in the room, ranked the same way as at game end
+pub async fn handle_request_scoreboard(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let message = match state.get_room(room_code) {
+        Some(room) if room.players.contains_key(&player_id) => crate::models::ServerMessage::Scoreboard {
+            rankings: crate::scoring::rank_players(room.players.values()),
+        },
+        _ => crate::models::ServerMessage::Error {
+            message: "You are not in this room".to_string(),
+            severity: crate::models::ErrorSeverity::Warning,
+        },
+    };
+    if let Ok(json) = serde_json::to_string(&message) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
+/// Outcome of a skip vote or give-up request
+enum SkipRequest {
+    EndRound(u64), // Round token of the round to end
+    Pending,
+    Rejected(String),
+}
+
+/// Handle a guesser's vote to skip the current drawer; a strict majority of guessers ends the round
+pub async fn handle_vote_skip(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let request = state.with_room_mut(room_code, |room| {
+        if room.game_state != crate::models::GameState::Playing || room.current_drawer.is_none() {
+            return SkipRequest::Rejected("There is no round to skip".to_string());
+        }
+        if room.current_drawer == Some(player_id)
+            || room.players.get(&player_id).is_none_or(|p| p.state == PlayerState::Spectator) {
+            return SkipRequest::Rejected("Only guessers can vote to skip".to_string());
+        }
+        if let Some(remaining) = room.skip_cooldown_remaining(chrono::Utc::now()) {
+            return SkipRequest::Rejected(format!("The drawer can't be skipped yet ({}s left)", remaining));
+        }
+        if !room.skip_votes.contains(&player_id) {
+            room.skip_votes.push(player_id);
+        }
+        if room.skip_votes.len() * 2 > room.potential_guessers() {
+            SkipRequest::EndRound(room.round_token)
+        } else {
+            SkipRequest::Pending
+        }
+    });
+    finish_skip_request(state, room_code, request, DrawerChangeReason::Skip, tx);
+}
+
+/// Handle the drawer giving up their turn
+pub async fn handle_give_up(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let request = state.with_room_mut(room_code, |room| {
+        if room.game_state != crate::models::GameState::Playing || room.current_drawer != Some(player_id) {
+            return SkipRequest::Rejected("Only the current drawer can give up".to_string());
+        }
+        if let Some(remaining) = room.skip_cooldown_remaining(chrono::Utc::now()) {
+            return SkipRequest::Rejected(format!("You can't give up yet ({}s left)", remaining));
+        }
+        SkipRequest::EndRound(room.round_token)
+    });
+    finish_skip_request(state, room_code, request, DrawerChangeReason::GiveUp, tx);
+}
+
+/// Handle a guesser buying a hint: deduct hint_cost (never below zero) and reveal one
+/// letter of the word they haven't bought yet, to them alone
+pub async fn handle_buy_hint(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    let purchase = state.with_room_mut(room_code, |room| {
+        let Some(word) = room.word.clone().filter(|_| room.game_state == crate::models::GameState::Playing) else {
+            return Err("There is no word to hint at".to_string());
+        };
+        if room.current_drawer == Some(player_id)
+            || room.winners.contains(&player_id)
+            || room.players.get(&player_id).is_none_or(|p| p.state == PlayerState::Spectator) {
+            return Err("Only guessers still guessing can buy hints".to_string());
+        }
+        let bought = room.hint_purchases.entry(player_id).or_default();
+        if bought.len() >= room.max_hints_per_round as usize {
+            return Err("No more hints available this round".to_string());
+        }
+        let hidden: Vec<(usize, char)> = word
+            .chars()
+            .enumerate()
+            .filter(|(i, c)| !c.is_whitespace() && !bought.contains(i))
+            .collect();
+        let Some(&(index, letter)) = hidden.choose(&mut rand::thread_rng()) else {
+            return Err("Every letter is already revealed".to_string());
+        };
+        bought.push(index);
+        let cost = room.hint_cost;
+        if let Some(player) = room.players.get_mut(&player_id) {
+            player.deduct_score(cost);
+        }
+        Ok(crate::models::ServerMessage::HintReveal { index, letter, cost })
+    });
+    let (message, succeeded) = match purchase {
+        Some(Ok(reveal)) => (reveal, true),
+        Some(Err(message)) => (crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning }, false),
+        None => return,
+    };
+    if let Ok(json) = serde_json::to_string(&message) {
+        let _ = tx.send(Message::Text(json));
+    }
+    // Everyone sees the new score, but not the letter
+    if succeeded {
         state.broadcast_room_state_filtered(room_code);
     }
 }
+
+fn finish_skip_request(
+    state: &AppState,
+    room_code: &str,
+    request: Option<SkipRequest>,
+    reason: DrawerChangeReason,
+    tx: &ConnectionSender,
+) {
+    match request {
+        Some(SkipRequest::EndRound(round_token)) => {
+            if let Some(outcome) = state.end_round_if_current(room_code, round_token) {
+                broadcast_round_outcome(state, room_code, &outcome, reason);
+            }
+        }
+        Some(SkipRequest::Pending) => state.broadcast_room_state_filtered(room_code),
+        Some(SkipRequest::Rejected(message)) => {
+            let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+        }
+        None => println!("Room {} not found for skip request", room_code),
+    }
+}
+
+/// Handle the drawer or the host ending the current round early. The skip cooldown still
+/// applies, so this can't cut a round shorter than a skip vote could.
+pub async fn handle_end_round(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    tx: &ConnectionSender,
+) {
+    println!("handle_end_round called for room: {}", room_code);
+
+    let request = state.with_room_mut(room_code, |room| {
+        if room.game_state != crate::models::GameState::Playing || room.current_drawer.is_none() {
+            return SkipRequest::Rejected("There is no round to end".to_string());
+        }
+        if room.current_drawer != Some(player_id) && room.host_id != player_id {
+            return SkipRequest::Rejected("Only the drawer or the host can end the round".to_string());
+        }
+        if let Some(remaining) = room.skip_cooldown_remaining(chrono::Utc::now()) {
+            return SkipRequest::Rejected(format!("The round can't be ended yet ({}s left)", remaining));
+        }
+        SkipRequest::EndRound(room.round_token)
+    });
+    finish_skip_request(state, room_code, request, DrawerChangeReason::Rotation, tx);
+}
+
+/// End the game once its wall-clock limit runs out. Starting another game replaces the
+/// clock, and removing the room stops it, so it only ever ends the game it was started for.
+fn start_game_clock(state: &AppState, room_code: &str, limit_secs: u32) {
+    let clock_state = state.clone();
+    let clock_room_code = room_code.to_string();
+    let clock = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(limit_secs as u64)).await;
+        if let Some(outcome) = clock_state.end_round_if_game_expired(&clock_room_code) {
+            println!("Game in room {} ran out of time", clock_room_code);
+            broadcast_round_outcome(&clock_state, &clock_room_code, &outcome, DrawerChangeReason::Rotation);
+        }
+    });
+    state.replace_game_clock(room_code, clock.abort_handle());
+}
+
+/// Close a finished room if nobody has voted to play again by the timeout. The round token
+/// identifies the finished game, so a rematch that started (and maybe ended) meanwhile keeps the room.
+fn start_post_game_timeout(state: &AppState, room_code: &str, round_token: u64, timeout_secs: u64) {
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(timeout_secs)).await;
+        let abandoned = state.get_room(&room_code).is_some_and(|room| {
+            room.game_state == crate::models::GameState::Finished
+                && room.round_token == round_token
+                && room.play_again_votes.is_empty()
+        });
+        if !abandoned {
+            return;
+        }
+        println!("Closing room {}: nobody voted to play again", room_code);
+        let closed_msg = crate::models::ServerMessage::RoomClosed {
+            room_code: room_code.clone(),
+            reason: "Nobody voted to play again".to_string(),
+        };
+        if let Ok(json) = serde_json::to_string(&closed_msg) {
+            state.broadcast_to_room(&room_code, Message::Text(json));
+        }
+        state.close_room(&room_code);
+    });
+}
+
+/// Start the backend timer that ends the round automatically, replacing any earlier timer
+/// for the room. The round token ties the timer to this round so it can't end a later one.
+pub(crate) fn start_round_timer(state: &AppState, room_code: &str, round_duration: u32, round_token: u64) {
+    let room_code_clone = room_code.to_string();
+    let state_clone = state.clone();
+
+    let timer = tokio::spawn(async move {
+        tokio::time::sleep(tokio::time::Duration::from_secs(round_duration as u64)).await;
+        expire_round(&state_clone, &room_code_clone, round_token).await;
+    });
+    state.replace_round_timer(room_code, timer.abort_handle());
+}
+
+pub(crate) async fn expire_round(state: &AppState, room_code: &str, round_token: u64) {
+    if let Some(outcome) = state.end_round_if_current(room_code, round_token) {
+        println!("Backend timer expired, ended round in room {}", room_code);
+        broadcast_round_outcome(state, room_code, &outcome, DrawerChangeReason::Rotation);
+    } else {
+        println!("Backend timer expired but round is no longer active - not ending round");
+    }
+}
+
+/// Broadcast everything clients need after a round has ended
+pub(crate) fn broadcast_round_outcome(
+    state: &AppState,
+    room_code: &str,
+    outcome: &RoundOutcome,
+    reason: DrawerChangeReason,
+) {
+    // Broadcast round scores
+    let round_scores_msg = crate::models::ServerMessage::RoundScores { scores: outcome.scores.clone() };
+    if let Ok(json) = serde_json::to_string(&round_scores_msg) {
+        state.broadcast_to_room(room_code, Message::Text(json));
+    }
+
+    for change in &outcome.score_changes {
+        let score_msg = crate::models::ServerMessage::ScoreUpdate {
+            player_id: change.player_id,
+            new_score: change.new_score,
+            delta: change.delta.into(),
+        };
+        if let Ok(json) = serde_json::to_string(&score_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+    }
+
+    state.record_event(room_code, RoomEventKind::RoundEnded {
+        round_number: outcome.scores.round_number,
+        word: outcome.scores.word.clone(),
+    });
+
+    // Single summary so clients don't have to stitch the granular messages together
+    let room = state.get_room(room_code);
+    let summary_msg = |next_drawer| crate::models::ServerMessage::RoundSummary {
+        word: outcome.scores.word.clone(),
+        scores: outcome.scores.clone(),
+        next_drawer,
+        intermission_secs: state.config.round_intermission_secs,
+    };
+    match (&room, &outcome.next_drawer) {
+        (Some(room), Some(next_drawer)) => broadcast_naming_drawer(state, room, next_drawer, summary_msg),
+        _ => {
+            if let Ok(json) = serde_json::to_string(&summary_msg(outcome.next_drawer.clone())) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+    }
+
+    if outcome.game_over {
+        // Game over - broadcast final scores
+        // The cycle counter has already moved past the last one played; sudden death doesn't add one
+        let (total_rounds_played, cycles_completed, words_used) = room
+            .as_ref()
+            .map(|r| (r.rounds_played, r.cycle_number.saturating_sub(1).min(r.max_rounds), r.used_words.clone()))
+            .unwrap_or_default();
+        let game_end_msg = crate::models::ServerMessage::GameEnded {
+            final_scores: outcome.final_scores.clone(),
+            rankings: outcome.rankings.clone(),
+            total_rounds_played,
+            cycles_completed,
+            words_used,
+        };
+        if let Ok(json) = serde_json::to_string(&game_end_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+        if let Some(seed) = room.as_ref().and_then(|r| r.word_seed) {
+            let reveal_msg = crate::models::ServerMessage::SeedReveal { seed: crate::words::to_hex(&seed) };
+            if let Ok(json) = serde_json::to_string(&reveal_msg) {
+                state.broadcast_to_room(room_code, Message::Text(json));
+            }
+        }
+        let timeout_secs = state.config.post_game_timeout_secs;
+        let post_game_msg = crate::models::ServerMessage::PostGame {
+            rankings: outcome.rankings.clone(),
+            play_again_timeout_secs: timeout_secs,
+        };
+        if let Ok(json) = serde_json::to_string(&post_game_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+        if let Some(room) = &room {
+            start_post_game_timeout(state, room_code, room.round_token, timeout_secs);
+        }
+        return; // Don't start next round
+    }
+
+    if !outcome.sudden_death_players.is_empty() {
+        let sudden_death_msg = crate::models::ServerMessage::SuddenDeath {
+            players: outcome.sudden_death_players.clone(),
+        };
+        if let Ok(json) = serde_json::to_string(&sudden_death_msg) {
+            state.broadcast_to_room(room_code, Message::Text(json));
+        }
+    }
+
+    // Announce the next drawer once the intermission is over
+    if let Some(drawer_player) = &outcome.next_drawer
+        && let Some(room) = &room {
+        let intermission_secs = state.config.round_intermission_secs;
+        if intermission_secs == 0 {
+            announce_next_round(state, room, drawer_player, &outcome.word_choices, reason);
+        } else {
+            let state = state.clone();
+            let room_code = room_code.to_string();
+            let round_token = room.round_token;
+            let drawer_player = drawer_player.clone();
+            let word_choices = outcome.word_choices.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(intermission_secs as u64)).await;
+                // The drawer may have left (or the game ended) during the pause
+                let Some(room) = state.get_room(&room_code).filter(|room| {
+                    room.round_token == round_token && room.current_drawer == Some(drawer_player.id)
+                }) else {
+                    return;
+                };
+                announce_next_round(&state, &room, &drawer_player, &word_choices, reason);
+            });
+        }
+    }
+
+    // Send filtered state so visibility is correct
+    state.broadcast_room_state_filtered(room_code);
+}
+
+/// Start the next round's word selection: RoundStart to the room, the choices to the drawer.
+/// A rotation is fully described by RoundStart; DrawerChanged is only added when the turn was
+/// cut short (skip, give-up, disconnect), since its reason is all it would tell clients.
+fn announce_next_round(
+    state: &AppState,
+    room: &crate::models::Room,
+    drawer_player: &Player,
+    word_choices: &[String],
+    reason: DrawerChangeReason,
+) {
+    broadcast_naming_drawer(state, room, drawer_player, |drawer| round_start_message(room, drawer));
+    state.record_event(&room.code, RoomEventKind::DrawerChanged { drawer_id: drawer_player.id, reason });
+    if reason != DrawerChangeReason::Rotation {
+        broadcast_naming_drawer(state, room, drawer_player, |drawer| {
+            crate::models::ServerMessage::DrawerChanged { drawer, reason }
+        });
+    }
+    send_word_choices(state, drawer_player.id, word_choices);
+    start_word_select_countdown(state, &room.code, room.round_token);
+}
+
+/// Broadcast a message that names the drawer; rooms that hide the drawer only tell the drawer themselves
+fn broadcast_naming_drawer(
+    state: &AppState,
+    room: &crate::models::Room,
+    drawer: &crate::models::Player,
+    build: impl Fn(Option<crate::models::Player>) -> crate::models::ServerMessage,
+) {
+    if room.reveal_drawer {
+        if let Ok(json) = serde_json::to_string(&build(Some(drawer.clone()))) {
+            state.broadcast_to_room(&room.code, Message::Text(json));
+        }
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(&build(Some(drawer.clone()))) {
+        state.send_to_player(&drawer.id, Message::Text(json));
+    }
+    if let Ok(json) = serde_json::to_string(&build(None)) {
+        state.broadcast_to_room_excluding(&room.code, Message::Text(json), drawer.id);
+    }
+}
+
+/// Outcome of validating a drawer's word selection
+enum WordSelection {
+    Accepted { word: String, drawer_id: Uuid, round_duration: u32, round_token: u64, pattern_delay_ms: u64, reveals: HintReveals },
+    Ignored(&'static str),
+    NotDrawer,
+    NotOffered,
+}
+
+/// Handle word selection
+pub async fn handle_word_selected(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    word: &str,
+    tx: &ConnectionSender,
+) {
+    // Persist the selected word and update round timings under the room lock
+    let selection = state.with_room_mut(room_code, |room| {
+        // Check if a word is already selected for this round
+        if room.word.is_some() {
+            return WordSelection::Ignored("word already selected");
+        }
+
+        // Check if the game is in playing state
+        if room.game_state != crate::models::GameState::Playing {
+            return WordSelection::Ignored("game not in playing state");
+        }
+
+        // Check if there's a current drawer
+        let Some(drawer_id) = room.current_drawer else {
+            return WordSelection::Ignored("no current drawer");
+        };
+        // Only the drawer picks; anyone else naming one of the offered words is refused
+        if drawer_id != player_id {
+            return WordSelection::NotDrawer;
+        }
+
+        // Only a word from the offered choices may be picked
+        let Some(chosen) = crate::words::match_word_choice(&room.pending_word_choices, word).cloned() else {
+            return WordSelection::NotOffered;
+        };
+
+        room.word = Some(chosen.clone());
+        room.pending_word_choices.clear();
+        // A canvas kept up through the intermission is wiped now; the state broadcast below syncs clients
+        if room.clear_canvas_on == crate::models::ClearTiming::WordSelect {
+            room.drawing_paths.clear();
+        }
+        room.round_start_time = Some(chrono::Utc::now());
+        room.round_end_time = Some(chrono::Utc::now() + chrono::Duration::seconds(room.round_duration as i64));
+
+        WordSelection::Accepted {
+            drawer_id,
+            round_duration: room.round_duration,
+            round_token: room.round_token,
+            pattern_delay_ms: (room.show_pattern_after * room.round_duration as f64 * 1000.0) as u64,
+            reveals: HintReveals::for_word(&chosen, room.start_reveal, room.hint_count, state.config.max_hint_reveal),
+            word: chosen,
+        }
+    });
+
+    let (word, round_duration, round_token, pattern_delay_ms, reveals) = match selection {
+        Some(WordSelection::Accepted { word, drawer_id, round_duration, round_token, pattern_delay_ms, reveals }) => {
+            state.record_event(room_code, RoomEventKind::WordSelected { drawer_id });
+            (word, round_duration, round_token, pattern_delay_ms, reveals)
+        }
+        Some(WordSelection::Ignored(reason)) => {
+            println!("Ignoring word selection in room {} ({}): {}", room_code, reason, word);
+            return;
+        }
+        Some(WordSelection::NotDrawer) => {
+            println!("Rejected word selection in room {}: {} is not the drawer", room_code, player_id);
+            let error_msg = crate::models::ServerMessage::Error {
+                message: "Only the drawer can choose the word".to_string(),
+                severity: crate::models::ErrorSeverity::Warning,
+            };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+        Some(WordSelection::NotOffered) => {
+            println!("Rejected word selection in room {}: {} was not offered", room_code, word);
+            let error_msg = crate::models::ServerMessage::Error {
+                message: "Selected word is not one of the offered choices".to_string(),
+                severity: crate::models::ErrorSeverity::Warning,
+            };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+        None => {
+            println!("Room {} not found for word selection", room_code);
+            return;
+        }
+    };
+
+    println!("Word selected in room {}: {} (starting {}s timer)", room_code, word, round_duration);
+
+    start_round_timer(state, room_code, round_duration, round_token);
+
+    // Broadcast filtered room state so all clients sync appropriately
+    state.broadcast_room_state_filtered(room_code);
+
+    // Do NOT broadcast the word globally; state filtering will reveal it only to winners
+    // Instead, send WordSelected with the word to winners, and an empty word to non-winners
+    let word_msg_winners = crate::models::ServerMessage::WordSelected {
+        word: word.clone(),
+    };
+    if let Ok(json) = serde_json::to_string(&word_msg_winners) {
+        state.broadcast_to_winners(room_code, Message::Text(json));
+    }
+
+    // Empty string ensures frontend treats it as hidden but still starts timers
+    let word_msg_non_winners = crate::models::ServerMessage::WordSelected {
+        word: String::new(),
+    };
+    if let Ok(json) = serde_json::to_string(&word_msg_non_winners) {
+        state.broadcast_to_non_winners(room_code, Message::Text(json));
+    }
+
+    // Letters are revealed in one random order: the first start_reveal at once, the rest on the schedule
+    let mut reveal_order = crate::words::letter_positions(&word);
+    reveal_order.shuffle(&mut rand::thread_rng());
+    reveal_order.truncate(reveals.at_start + reveals.scheduled);
+    send_word_pattern(state, room_code, &word, &reveal_order[..reveals.at_start], round_token, pattern_delay_ms);
+    start_hint_schedule(state, room_code, &word, round_token, round_duration, reveal_order, reveals.at_start);
+}
+
+/// How a round's letter reveals split between the first pattern and the back-half schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HintReveals {
+    at_start: usize,
+    scheduled: usize,
+}
+
+impl HintReveals {
+    // Both kinds come out of one budget: max_hint_reveal, and never the whole word
+    fn for_word(word: &str, start_reveal: u32, hint_count: u32, max_hint_reveal: u32) -> Self {
+        let total = crate::words::hint_reveal_count(word, start_reveal.saturating_add(hint_count), max_hint_reveal);
+        let at_start = (start_reveal as usize).min(total);
+        Self { at_start, scheduled: total - at_start }
+    }
+}
+
+/// When each of a round's hint reveals fires, in ms from the round start: spread evenly
+/// over the back half, with none landing on the final second
+pub(crate) fn hint_reveal_times_ms(round_duration: u32, reveals: usize) -> Vec<u64> {
+    let half_ms = round_duration as u64 * 1000 / 2;
+    let step = half_ms / (reveals as u64 + 1);
+    (1..=reveals as u64).map(|k| half_ms + k * step).collect()
+}
+
+/// Reveal the next letter of `reveal_order` to guessers at each scheduled time, after the
+/// `already_shown` ones the first pattern carried, stopping as soon as the round moves on
+fn start_hint_schedule(
+    state: &AppState,
+    room_code: &str,
+    word: &str,
+    round_token: u64,
+    round_duration: u32,
+    reveal_order: Vec<usize>,
+    already_shown: usize,
+) {
+    let reveals = reveal_order.len().saturating_sub(already_shown);
+    if reveals == 0 {
+        return;
+    }
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    let word = word.to_string();
+    tokio::spawn(async move {
+        let started = tokio::time::Instant::now();
+        for (n, at_ms) in hint_reveal_times_ms(round_duration, reveals).into_iter().enumerate() {
+            tokio::time::sleep_until(started + std::time::Duration::from_millis(at_ms)).await;
+            let still_current = state.get_room(&room_code).is_some_and(|room| room.round_token == round_token && room.word.is_some());
+            if !still_current {
+                return;
+            }
+            let revealed = &reveal_order[..already_shown + n + 1];
+            let hint_msg = crate::models::ServerMessage::WordHint {
+                pattern: crate::words::word_pattern_revealing(&word, revealed),
+            };
+            if let Ok(json) = serde_json::to_string(&hint_msg) {
+                state.broadcast_to_non_winners(&room_code, Message::Text(json));
+            }
+        }
+    });
+}
+
+/// Send guessers the word's pattern, straight away or once the room's show_pattern_after
+/// share of the round has passed. Nothing is sent if the round is over by then.
+fn send_word_pattern(state: &AppState, room_code: &str, word: &str, revealed: &[usize], round_token: u64, delay_ms: u64) {
+    let hint_msg = crate::models::ServerMessage::WordHint { pattern: crate::words::word_pattern_revealing(word, revealed) };
+    let Ok(json) = serde_json::to_string(&hint_msg) else {
+        return;
+    };
+    if delay_ms == 0 {
+        state.broadcast_to_non_winners(room_code, Message::Text(json));
+        return;
+    }
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        if state.get_room(&room_code).is_some_and(|room| room.round_token == round_token && room.word.is_some()) {
+            state.broadcast_to_non_winners(&room_code, Message::Text(json));
+        }
+    });
+}
+
+/// RoundStart for the room's current round, naming `drawer` (None when hidden from the recipient)
+fn round_start_message(room: &crate::models::Room, drawer: Option<Player>) -> crate::models::ServerMessage {
+    crate::models::ServerMessage::RoundStart {
+        room_code: room.code.clone(),
+        drawer,
+        score_multiplier: room.score_multiplier(),
+        warmup: room.is_warmup_round(),
+        round_number: room.round_number,
+        cycle_number: room.cycle_number,
+        max_rounds: room.max_rounds,
+        round_duration: room.round_duration,
+        rounds_remaining: room.rounds_remaining,
+    }
+}
+
+/// Privately offer the drawer their word choices for the new round
+pub(crate) fn send_word_choices(state: &AppState, drawer_id: Uuid, words: &[String]) {
+    let choices_msg = crate::models::ServerMessage::WordChoices { words: words.to_vec() };
+    if let Ok(json) = serde_json::to_string(&choices_msg) {
+        state.send_to_player(&drawer_id, Message::Text(json));
+    }
+}
+
+/// Broadcast the seconds left for the drawer to pick a word, once a second, until a word is
+/// selected or the round moves on. The countdown is informational; nothing happens at zero.
+fn start_word_select_countdown(state: &AppState, room_code: &str, round_token: u64) {
+    let secs = state.config.word_select_secs;
+    if secs == 0 {
+        return;
+    }
+    let state = state.clone();
+    let room_code = room_code.to_string();
+    tokio::spawn(async move {
+        for seconds_remaining in (1..=secs).rev() {
+            let Some(room) = state.get_room(&room_code) else {
+                return;
+            };
+            if room.round_token != round_token || room.word.is_some() {
+                return;
+            }
+            let Some(drawer) = room.current_drawer.and_then(|id| room.players.get(&id).cloned()) else {
+                return;
+            };
+            broadcast_naming_drawer(&state, &room, &drawer, |drawer| {
+                crate::models::ServerMessage::SelectingWord { drawer, seconds_remaining }
+            });
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+}
+
+/// Update room settings (host-only). Supports max_rounds (1..=5) and
+/// guess_delay_secs (capped below the round duration). max_players may drop below the
+/// current head count: nobody is removed, new players are just turned away (or seated as
+/// spectators) until enough have left.
+pub async fn handle_update_settings(
+    state: &AppState,
+    room_code: &str,
+    player_id: Uuid,
+    settings: &RoomSettingsUpdate,
+    tx: &ConnectionSender,
+) {
+    let updated = state.with_room_mut(room_code, |room| {
+        if room.host_id != player_id {
+            return Err("Only the host can change room settings".to_string());
+        }
+        // These decide how long a game can run, so check them together before applying anything
+        let max_rounds = settings.max_rounds.map_or(room.max_rounds, |rounds| rounds.clamp(1, 5));
+        let min_players = if room.practice { 1 } else { 2 };
+        let max_players = settings
+            .max_players
+            .map_or(room.max_players, |players| players.clamp(min_players, MAX_PLAYERS_PER_ROOM));
+        let max_game_duration_secs = settings
+            .max_game_duration_secs
+            .map_or(room.max_game_duration_secs, |limit| (limit > 0).then_some(limit));
+        check_game_length(
+            state.config.max_total_game_secs,
+            max_players,
+            max_rounds,
+            room.round_duration,
+            max_game_duration_secs,
+        )?;
+        room.max_rounds = max_rounds;
+        room.max_players = max_players;
+        room.max_game_duration_secs = max_game_duration_secs;
+
+        if let Some(guess_delay_secs) = settings.guess_delay_secs {
+            room.guess_delay_secs = guess_delay_secs.min(room.round_duration.saturating_sub(1));
+        }
+        if let Some(hint_count) = settings.hint_count {
+            room.hint_count = hint_count.min(state.config.max_hint_reveal);
+        }
+        if let Some(start_reveal) = settings.start_reveal {
+            room.start_reveal = start_reveal.min(state.config.max_hint_reveal);
+        }
+        if let Some(fraction) = settings.show_pattern_after.filter(|f| f.is_finite()) {
+            room.show_pattern_after = fraction.clamp(0.0, 1.0);
+        }
+        if let Some(multiplier) = settings.final_round_multiplier.filter(|m| m.is_finite()) {
+            room.final_round_multiplier = multiplier.clamp(1.0, 3.0);
+        }
+        if let Some(max_undo) = settings.max_undo {
+            room.max_undo = Some(max_undo);
+        }
+        if let Some(width) = settings.canvas_width {
+            room.canvas_width = width.clamp(100, 4000);
+        }
+        if let Some(height) = settings.canvas_height {
+            room.canvas_height = height.clamp(100, 4000);
+        }
+        if let Some(secs) = settings.min_draw_time_before_skip {
+            room.min_draw_time_before_skip = secs.min(room.round_duration);
+        }
+        if let Some(allow) = settings.allow_spectators {
+            room.allow_spectators = allow;
+        }
+        if let Some(max_spectators) = settings.max_spectators {
+            room.max_spectators = max_spectators;
+        }
+        if let Some(matching) = settings.guess_matching {
+            room.guess_matching = matching;
+        }
+        if let Some(timing) = settings.clear_canvas_on {
+            room.clear_canvas_on = timing;
+        }
+        if let Some(cap) = settings.winners_chat_cap {
+            room.winners_chat_cap = cap;
+        }
+        if let Some(allow) = settings.allow_spectator_chat {
+            room.allow_spectator_chat = allow;
+        }
+        if let Some(show) = settings.show_draw_order {
+            room.show_draw_order = show;
+        }
+        if let Some(sees) = settings.drawer_sees_guesses {
+            room.drawer_sees_guesses = sees;
+        }
+        if let Some(verifiable) = settings.verifiable_words {
+            room.verifiable_words = verifiable;
+        }
+        if let Some(reveal) = settings.reveal_drawer {
+            room.reveal_drawer = reveal;
+        }
+        if let Some(warmup) = settings.warmup_first_round {
+            room.warmup_first_round = warmup;
+        }
+        if let Some(sudden_death) = settings.sudden_death {
+            room.sudden_death = sudden_death;
+        }
+        if let Some(require_ready) = settings.require_ready {
+            room.require_ready = require_ready;
+        }
+        if let Some(cost) = settings.hint_cost {
+            room.hint_cost = cost;
+        }
+        if let Some(max_hints) = settings.max_hints_per_round {
+            room.max_hints_per_round = max_hints;
+        }
+        Ok(())
+    });
+    match updated {
+        Some(Ok(())) => {}
+        Some(Err(message)) => {
+            let error_msg = crate::models::ServerMessage::Error { message, severity: crate::models::ErrorSeverity::Warning };
+            if let Ok(json) = serde_json::to_string(&error_msg) {
+                let _ = tx.send(Message::Text(json));
+            }
+            return;
+        }
+        None => {
+            println!("Failed to update room settings: room {} not found", room_code);
+            return;
+        }
+    }
+    // Broadcast full room state so all clients sync
+    state.broadcast_room_state_filtered(room_code);
+}
+
+/// Reject settings whose longest possible game (every seat filled, every round run to the
+/// clock) would outlast the server's ceiling. A wall-clock game limit caps the estimate.
+fn check_game_length(
+    ceiling_secs: u64,
+    max_players: u8,
+    max_rounds: u32,
+    round_duration: u32,
+    max_game_duration_secs: Option<u32>,
+) -> Result<(), String> {
+    let uncapped = max_players as u64 * max_rounds as u64 * round_duration as u64;
+    let longest = max_game_duration_secs.map_or(uncapped, |limit| uncapped.min(limit as u64));
+    if ceiling_secs == 0 || longest <= ceiling_secs {
+        return Ok(());
+    }
+    Err(format!(
+        "These settings allow a game of up to {} minutes ({} players x {} rounds x {}s), over the {} minute limit. \
+         Lower the rounds or players, or set a max game duration.",
+        longest.div_ceil(60),
+        max_players,
+        max_rounds,
+        round_duration,
+        ceiling_secs / 60,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ServerMessage;
+    use crate::test_support::{
+        connect, countdown_ticks, drain, drained, draw_orders, hint_patterns, room_with_players, round_with_guesser, start_round,
+        without_intermission, word_choices,
+    };
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_guess_and_timer_end_round_once() {
+        let state = AppState::new();
+        let (room_code, ids, mut rx) = round_with_guesser(&state, "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+
+        let guesser = ids[1];
+        let guess_state = state.clone();
+        let guess_code = room_code.clone();
+        let guess = tokio::spawn(async move {
+            let (tx, _rx) = crate::state::connection_channel();
+            crate::websocket::chat::handle_chat(&guess_state, &guess_code, "apple", guesser, "guesser", &tx).await;
+        });
+        let timer_state = state.clone();
+        let timer_code = room_code.clone();
+        let timer = tokio::spawn(async move {
+            expire_round(&timer_state, &timer_code, round_token).await;
+        });
+        guess.await.unwrap();
+        timer.await.unwrap();
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.round_token, round_token + 1);
+        assert_eq!(room.current_drawer, Some(ids[1]));
+        let round_scores = drain(&mut rx)
+            .into_iter()
+            .filter(|m| matches!(m, ServerMessage::RoundScores { .. }))
+            .count();
+        assert_eq!(round_scores, 1);
+    }
+
+    #[test]
+    fn test_end_round_rotates_drawer_and_resets_round() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+
+        let outcome = state.end_round(&room_code).unwrap();
+        assert!(!outcome.game_over);
+        assert_eq!(outcome.next_drawer.unwrap().id, ids[1]);
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.round_number, 2);
+        assert_eq!(room.word, None);
+        assert_eq!(room.winners, vec![ids[1]]);
+    }
+
+    #[tokio::test]
+    async fn test_each_completed_round_sends_one_round_summary() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[1]);
+
+        let (tx, _rx) = crate::state::connection_channel();
+        handle_end_round(&state, &room_code, ids[0], &tx).await;
+
+        let summaries: Vec<_> = drained!(&mut rx, ServerMessage::RoundSummary { word, scores, next_drawer, .. } => (word, scores, next_drawer));
+        assert_eq!(summaries.len(), 1);
+        let (word, scores, next_drawer) = &summaries[0];
+        assert_eq!(word, "apple");
+        assert_eq!(scores.round_number, 1);
+        assert_eq!(next_drawer.as_ref().unwrap().id, ids[1]);
+    }
+
+    #[tokio::test]
+    async fn test_next_round_waits_out_the_intermission() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            round_intermission_secs: 1,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[1]);
+        let (tx, _rx) = crate::state::connection_channel();
+
+        handle_end_round(&state, &room_code, ids[0], &tx).await;
+        let messages = drain(&mut rx);
+        assert!(messages.iter().any(|m| matches!(m, ServerMessage::RoundSummary { intermission_secs: 1, .. })));
+        assert!(!messages.iter().any(|m| matches!(m, ServerMessage::RoundStart { .. } | ServerMessage::WordChoices { .. })));
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let messages = drain(&mut rx);
+        assert!(messages.iter().any(|m| matches!(m, ServerMessage::RoundStart { drawer: Some(d), .. } if d.id == ids[1])));
+        assert!(messages.iter().any(|m| matches!(m, ServerMessage::WordChoices { .. })));
+        // A plain rotation is fully described by RoundStart
+        assert!(!messages.iter().any(|m| matches!(m, ServerMessage::DrawerChanged { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_score_update_sent_per_scored_player() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            all_guessed_advance_ms: 60_000,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[2]);
+        let (tx, _rx) = crate::state::connection_channel();
+
+        crate::websocket::chat::handle_chat(&state, &room_code, "apple", ids[1], "b", &tx).await;
+        handle_end_round(&state, &room_code, ids[0], &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let mut updates: Vec<(Uuid, u32)> = drained!(&mut rx, ServerMessage::ScoreUpdate { player_id, new_score, delta } => {
+            assert_eq!(new_score, room.players[&player_id].score);
+            assert!(delta > 0);
+            (player_id, new_score)
+        });
+        updates.sort();
+        let mut expected = vec![(ids[0], room.players[&ids[0]].score), (ids[1], room.players[&ids[1]].score)];
+        expected.sort();
+        assert_eq!(updates, expected);
+    }
+
+    /// The room's current drawer, who alone may pick the word
+    fn drawer_of(state: &AppState, room_code: &str) -> Uuid {
+        state.get_room(room_code).unwrap().current_drawer.unwrap()
+    }
+
+    /// Start a game and return the drawer id plus the words they were offered
+    async fn start_game_with_choices(state: &AppState, room_code: &str, ids: &[Uuid]) -> (Uuid, Vec<String>) {
+        let mut receivers: Vec<_> = ids.iter().map(|&id| (id, connect(state, room_code, id))).collect();
+        let (tx, _rx) = crate::state::connection_channel();
+        handle_start_game(state, room_code, ids[0], false, &tx).await;
+
+        let drawer = state.get_room(room_code).unwrap().current_drawer.unwrap();
+        let (_, drawer_rx) = receivers.iter_mut().find(|(id, _)| *id == drawer).unwrap();
+        let words = word_choices(drawer_rx).expect("drawer should be offered word choices");
+        (drawer, words)
+    }
+
+    #[tokio::test]
+    async fn test_word_selected_from_offered_choices_is_accepted() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (_, words) = start_game_with_choices(&state, &room_code, &ids).await;
+        assert_eq!(words.len(), crate::words::WORD_CHOICE_COUNT);
+
+        let (tx, _rx) = crate::state::connection_channel();
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), &words[1].to_uppercase(), &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.word.as_ref(), Some(&words[1]));
+        assert!(room.pending_word_choices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_word_selected_by_non_drawer_is_rejected() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (drawer, words) = start_game_with_choices(&state, &room_code, &ids).await;
+        let guesser = if drawer == ids[0] { ids[1] } else { ids[0] };
+
+        let (tx, mut rx) = crate::state::connection_channel();
+        handle_word_selected(&state, &room_code, guesser, &words[0], &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.word, None);
+        assert_eq!(room.pending_word_choices, words);
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_word_selected_off_list_is_rejected() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        start_game_with_choices(&state, &room_code, &ids).await;
+
+        let (tx, mut rx) = crate::state::connection_channel();
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), "notawordwewouldoffer", &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.word, None);
+        assert_eq!(room.pending_word_choices.len(), crate::words::WORD_CHOICE_COUNT);
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_accepts_legacy_and_new_fields() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (tx, _rx) = crate::state::connection_channel();
+
+        let legacy = format!(r#"{{"type":"UpdateSettings","room_code":"{}","max_rounds":4}}"#, room_code);
+        let crate::models::ClientMessage::UpdateSettings { settings, .. } = serde_json::from_str(&legacy).unwrap() else {
+            panic!("expected UpdateSettings");
+        };
+        handle_update_settings(&state, &room_code, ids[0], &settings, &tx).await;
+
+        let delay = format!(r#"{{"type":"UpdateSettings","room_code":"{}","guess_delay_secs":5}}"#, room_code);
+        let crate::models::ClientMessage::UpdateSettings { settings, .. } = serde_json::from_str(&delay).unwrap() else {
+            panic!("expected UpdateSettings");
+        };
+        handle_update_settings(&state, &room_code, ids[0], &settings, &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!((room.max_rounds, room.guess_delay_secs), (4, 5));
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_rejected_from_non_host() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b"]);
+        let (tx, mut rx) = crate::state::connection_channel();
+        let settings = RoomSettingsUpdate { max_rounds: Some(5), reveal_drawer: Some(false), ..RoomSettingsUpdate::default() };
+
+        handle_update_settings(&state, &room_code, ids[1], &settings, &tx).await;
+
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+        let room = state.get_room(&room_code).unwrap();
+        assert_ne!(room.max_rounds, 5);
+        assert!(room.reveal_drawer);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_rejects_overlong_games() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        state.with_room_mut(&room_code, |room| room.round_duration = 300);
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        // 8 players x 5 rounds x 300s is over three hours
+        let extreme = RoomSettingsUpdate { max_rounds: Some(5), max_players: Some(8), ..RoomSettingsUpdate::default() };
+        handle_update_settings(&state, &room_code, ids[0], &extreme, &tx).await;
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { message, .. }] if message.contains("200 minutes")));
+        let room = state.get_room(&room_code).unwrap();
+        assert_ne!(room.max_rounds, 5);
+
+        // The same table with a wall-clock cap fits
+        let capped = RoomSettingsUpdate { max_game_duration_secs: Some(3600), ..extreme };
+        handle_update_settings(&state, &room_code, ids[0], &capped, &tx).await;
+        assert!(!drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!((room.max_rounds, room.max_players, room.max_game_duration_secs), (5, 8, Some(3600)));
+    }
+
+    #[tokio::test]
+    async fn test_drawer_disconnect_mid_round_sends_drawer_changed() {
+        let state = without_intermission();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let _drawer_rx = connect(&state, &room_code, ids[0]);
+        let mut rx = connect(&state, &room_code, ids[2]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+
+        let changed: Vec<_> = drained!(&mut rx, ServerMessage::DrawerChanged { drawer: Some(drawer), reason } => (drawer.id, reason));
+        assert_eq!(changed, vec![(ids[1], DrawerChangeReason::Disconnect)]);
+        assert_eq!(state.get_room(&room_code).unwrap().current_drawer, Some(ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_drawer_disconnect_while_choosing_passes_choices_on() {
+        let state = without_intermission();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.word = None;
+            room.pending_word_choices = vec!["apple".to_string(), "pear".to_string(), "plum".to_string()];
+        });
+        let _drawer_rx = connect(&state, &room_code, ids[0]);
+        let mut next_rx = connect(&state, &room_code, ids[1]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+
+        let messages = drain(&mut next_rx);
+        assert!(messages.iter().any(|m| matches!(m, ServerMessage::WordChoices { words } if words.len() == 3)));
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            ServerMessage::DrawerChanged { drawer: Some(drawer), reason: DrawerChangeReason::Disconnect } if drawer.id == ids[1]
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_selecting_drawer_keeps_turn_when_skip_disabled() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            skip_selecting_drawer_on_disconnect: false,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.word = None);
+        let _drawer_rx = connect(&state, &room_code, ids[0]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+
+        assert_eq!(state.get_room(&room_code).unwrap().current_drawer, Some(ids[0]));
+    }
+
+    #[tokio::test]
+    async fn test_game_ended_recaps_rounds_cycles_and_words() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        state.with_room_mut(&room_code, |room| room.max_rounds = 1);
+        start_round(&state, &room_code, ids[0], "apple");
+        let mut rx = connect(&state, &room_code, ids[1]);
+
+        let outcome = state.end_round(&room_code).unwrap();
+        broadcast_round_outcome(&state, &room_code, &outcome, DrawerChangeReason::Rotation);
+        state.with_room_mut(&room_code, |room| room.word = Some("pear".to_string()));
+        let outcome = state.end_round(&room_code).unwrap();
+        assert!(outcome.game_over);
+        broadcast_round_outcome(&state, &room_code, &outcome, DrawerChangeReason::Rotation);
+
+        let recap = drained!(&mut rx, ServerMessage::GameEnded { total_rounds_played, cycles_completed, words_used, .. } => {
+            (total_rounds_played, cycles_completed, words_used)
+        }).into_iter().next();
+        assert_eq!(recap, Some((2, 1, vec!["apple".to_string(), "pear".to_string()])));
+    }
+
+    #[tokio::test]
+    async fn test_draw_order_broadcast_at_start_and_after_leave() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        let rotation = state.get_room(&room_code).unwrap().draw_order;
+        assert_eq!(draw_orders(&mut rx), vec![rotation.clone()]);
+
+        leave(&state, &room_code, ids[1]).await;
+        let remaining: Vec<Uuid> = rotation.into_iter().filter(|id| *id != ids[1]).collect();
+        assert_eq!(draw_orders(&mut rx), vec![remaining]);
+    }
+
+    #[tokio::test]
+    async fn test_revealed_seed_matches_commitment_and_replays_choices() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        state.with_room_mut(&room_code, |room| {
+            room.verifiable_words = true;
+            room.max_rounds = 1;
+        });
+        let mut rx_a = connect(&state, &room_code, ids[0]);
+        let mut rx_b = connect(&state, &room_code, ids[1]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        let mut messages = drain(&mut rx_a);
+        messages.extend(drain(&mut rx_b));
+        let commitment = messages.iter().find_map(|m| match m {
+            ServerMessage::SeedCommitment { seed_hash } => Some(seed_hash.clone()),
+            _ => None,
+        });
+        let offered = messages.iter().find_map(|m| match m {
+            ServerMessage::WordChoices { words } => Some(words.clone()),
+            _ => None,
+        });
+
+        while let Some(outcome) = state.end_round(&room_code) {
+            let game_over = outcome.game_over;
+            broadcast_round_outcome(&state, &room_code, &outcome, DrawerChangeReason::Rotation);
+            if game_over {
+                break;
+            }
+        }
+        let seed_hex = drained!(&mut rx_a, ServerMessage::SeedReveal { seed } => seed)
+            .into_iter()
+            .next()
+            .expect("seed is revealed at game end");
+
+        let seed: [u8; 32] = (0..32)
+            .map(|i| u8::from_str_radix(&seed_hex[i * 2..i * 2 + 2], 16).unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        assert_eq!(commitment, Some(crate::words::seed_commitment(&seed)));
+        let replayed = crate::words::pick_word_choices_with(
+            &[],
+            crate::words::DEFAULT_MIN_WORD_LENGTH,
+            crate::words::WORD_CHOICE_COUNT,
+            &[],
+            &mut crate::words::seeded_draw_rng(&seed, 0),
+        );
+        assert_eq!(offered, Some(replayed));
+    }
+
+    async fn adjust(state: &AppState, room_code: &str, by: Uuid, target: Uuid, delta: i64) -> Vec<ServerMessage> {
+        let (tx, mut rx) = crate::state::connection_channel();
+        let mut watcher = connect(state, room_code, Uuid::new_v4());
+        handle_adjust_score(state, room_code, by, target, delta, &tx).await;
+        let mut messages = drain(&mut rx);
+        messages.extend(drain(&mut watcher));
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_host_adjusts_score_up_and_down_to_zero_floor() {
+        let state = AppState::with_config(crate::config::ServerConfig { debug_endpoints: true, ..crate::config::ServerConfig::default() });
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+        state.with_room_mut(&room_code, |room| room.players.get_mut(&ids[1]).unwrap().score = 30);
+
+        let up = adjust(&state, &room_code, ids[0], ids[1], 25).await;
+        assert!(matches!(up.as_slice(), [ServerMessage::ScoreUpdate { new_score: 55, delta: 25, .. }]));
+
+        let down = adjust(&state, &room_code, ids[0], ids[1], -100).await;
+        assert!(matches!(down.as_slice(), [ServerMessage::ScoreUpdate { new_score: 0, delta: -55, .. }]));
+        assert!(state.room_events(&room_code).iter().any(|e| e.event
+            == RoomEventKind::ScoreAdjusted { by: ids[0], player_id: ids[1], delta: -55, new_score: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_non_host_cannot_adjust_score() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+
+        let messages = adjust(&state, &room_code, ids[1], ids[1], 500).await;
+
+        assert!(matches!(messages.as_slice(), [ServerMessage::Error { .. }]));
+        assert_eq!(state.get_room(&room_code).unwrap().players[&ids[1]].score, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lowering_max_players_keeps_everyone_but_blocks_joins() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        state.with_room_mut(&room_code, |room| room.allow_spectators = false);
+        let (tx, _rx) = crate::state::connection_channel();
+        let settings = RoomSettingsUpdate { max_players: Some(2), ..serde_json::from_str("{}").unwrap() };
+
+        handle_update_settings(&state, &room_code, ids[0], &settings, &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.max_players, 2);
+        assert!(ids.iter().all(|id| room.players.contains_key(id)));
+        assert!(state.add_player_to_room(&room_code, Player::new("late")).is_err());
+
+        // Once enough players have gone, the room opens again
+        state.remove_player_from_room(&room_code, &ids[2]).unwrap();
+        state.remove_player_from_room(&room_code, &ids[1]).unwrap();
+        assert!(state.add_player_to_room(&room_code, Player::new("late")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_join_room_is_not_announced_twice() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let mut other_rx = connect(&state, &room_code, ids[0]);
+        let (tx, mut own_rx) = crate::state::connection_channel();
+        let (mut current_player_id, mut current_room_code) = (None, None);
+
+        let token = state.issue_auth_token(ids[1]);
+        for _ in 0..2 {
+            handle_join_room(&state, &room_code, "b", &token, &tx, &mut current_player_id, &mut current_room_code).await;
+        }
+
+        let joins = |messages: Vec<ServerMessage>| messages.iter().filter(|m| matches!(m, ServerMessage::PlayerJoined { .. })).count();
+        assert_eq!(joins(drain(&mut other_rx)), 1);
+        assert_eq!(joins(drain(&mut own_rx)), 1);
+        assert_eq!(current_player_id, Some(ids[1]));
+    }
+
+    #[tokio::test]
+    async fn test_practice_room_cycles_words_solo() {
+        let state = without_intermission();
+        let (room_code, ids) = room_with_players(&state, &["solo"]);
+        state.with_room_mut(&room_code, |room| room.practice = true);
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        let mut chosen = Vec::new();
+        for _ in 0..2 {
+            let words = word_choices(&mut rx).expect("solo player should be offered words");
+            handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), &words[0], &tx).await;
+            chosen.push(state.get_room(&room_code).unwrap().word.unwrap());
+            handle_next_word(&state, &room_code, ids[0], &tx).await;
+        }
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(chosen.len(), 2);
+        assert_eq!(room.game_state, crate::models::GameState::Playing);
+        assert_eq!(room.current_drawer, Some(ids[0]));
+        assert_eq!(room.players[&ids[0]].score, 0);
+    }
+
+    #[tokio::test]
+    async fn test_next_word_refused_for_anyone_but_the_drawer() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "watcher"]);
+        state.with_room_mut(&room_code, |room| room.practice = true);
+        start_round(&state, &room_code, ids[0], "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_next_word(&state, &room_code, ids[1], &tx).await;
+
+        assert_eq!(state.get_room(&room_code).unwrap().round_token, round_token);
+        assert!(matches!(
+            drain(&mut rx).as_slice(),
+            [ServerMessage::Error { message, .. }] if message == "Only the drawer can skip to the next word"
+        ));
+    }
+
+    async fn leave(state: &AppState, room_code: &str, player_id: Uuid) {
+        let (tx, _rx) = crate::state::connection_channel();
+        let (mut current_player_id, mut current_room_code) = (Some(player_id), Some(room_code.to_string()));
+        handle_leave_room(state, room_code, &player_id.to_string(), &tx, &mut current_player_id, &mut current_room_code).await;
+    }
+
+    #[tokio::test]
+    async fn test_drawer_leaving_mid_selection_passes_the_turn() {
+        let state = without_intermission();
+        let (room_code, ids) = room_with_players(&state, &["host", "b", "c"]);
+        let (drawer, _) = start_game_with_choices(&state, &room_code, &ids).await;
+        let stayer = *ids.iter().find(|&&id| id != drawer).unwrap();
+        let mut rx = connect(&state, &room_code, stayer);
+
+        leave(&state, &room_code, drawer).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let next = room.current_drawer.expect("the turn should pass on");
+        assert_ne!(next, drawer);
+        assert!(room.players.contains_key(&next));
+        assert_eq!(room.pending_word_choices.len(), crate::words::WORD_CHOICE_COUNT);
+        assert!(drain(&mut rx).iter().any(|m| matches!(
+            m,
+            ServerMessage::DrawerChanged { drawer: Some(d), reason: DrawerChangeReason::Skip } if d.id == next
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_last_player_host_leaving_removes_room() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host"]);
+        let mut rx = connect(&state, &room_code, ids[0]);
+
+        leave(&state, &room_code, ids[0]).await;
+
+        assert!(state.get_room(&room_code).is_none());
+        assert!(!drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::HostChanged { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_host_leaving_with_others_transfers_host() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b"]);
+        let mut rx = connect(&state, &room_code, ids[1]);
+
+        leave(&state, &room_code, ids[0]).await;
+
+        assert_eq!(state.get_room(&room_code).unwrap().host_id, ids[1]);
+        let new_hosts: Vec<Uuid> = drained!(&mut rx, ServerMessage::HostChanged { new_host } => new_host.id);
+        assert_eq!(new_hosts, vec![ids[1]]);
+    }
+
+    #[tokio::test]
+    async fn test_host_socket_drop_transfers_host_to_connected_player() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "away", "here"]);
+        state.with_room_mut(&room_code, |room| room.players.get_mut(&ids[1]).unwrap().is_connected = false);
+        let mut rx = connect(&state, &room_code, ids[2]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.host_id, ids[2]);
+        assert!(room.players.contains_key(&ids[0])); // still seated for the grace window
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::HostChanged { new_host } if new_host.id == ids[2])));
+    }
+
+    #[tokio::test]
+    async fn test_skip_vote_rejected_until_min_draw_time() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.min_draw_time_before_skip = 30);
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_vote_skip(&state, &room_code, ids[1], &tx).await;
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+        assert!(state.get_room(&room_code).unwrap().skip_votes.is_empty());
+
+        state.with_room_mut(&room_code, |room| {
+            room.round_start_time = Some(chrono::Utc::now() - chrono::Duration::seconds(31));
+        });
+        handle_vote_skip(&state, &room_code, ids[1], &tx).await;
+        handle_vote_skip(&state, &room_code, ids[2], &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.round_token, round_token + 1);
+        assert!(!drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_end_round_only_from_drawer_or_host() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "artist", "c"]);
+        start_round(&state, &room_code, ids[1], "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_end_round(&state, &room_code, ids[2], &tx).await;
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+        assert_eq!(state.get_room(&room_code).unwrap().round_token, round_token);
+
+        handle_end_round(&state, &room_code, ids[0], &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().round_token, round_token + 1);
+    }
+
+    #[tokio::test]
+    async fn test_kick_is_fatal_and_closes_socket_while_rate_limit_only_warns() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.min_draw_time_before_skip = 30);
+        let (tx, mut rx) = crate::state::connection_channel();
+        state.add_connection(ids[1], room_code.clone(), tx.clone());
+        let (kicked_tx, mut kicked_rx) = crate::state::connection_channel();
+        state.add_connection(ids[2], room_code.clone(), kicked_tx.clone());
+
+        // Voting to skip too early is refused, but the connection stays up
+        handle_vote_skip(&state, &room_code, ids[1], &tx).await;
+        assert!(matches!(
+            drain(&mut rx).as_slice(),
+            [ServerMessage::Error { severity: crate::models::ErrorSeverity::Warning, .. }]
+        ));
+        let still_open = std::time::Duration::from_millis(20);
+        assert!(tokio::time::timeout(still_open, tx.closed()).await.is_err());
+
+        let (host_tx, _host_rx) = crate::state::connection_channel();
+        handle_kick_player(&state, &room_code, ids[0], ids[2], &host_tx).await;
+
+        let Ok(Message::Text(text)) = kicked_rx.try_recv() else {
+            panic!("kicked player should get an error first");
+        };
+        assert!(matches!(
+            serde_json::from_str(&text).unwrap(),
+            ServerMessage::Error { severity: crate::models::ErrorSeverity::Fatal, .. }
+        ));
+        assert!(matches!(kicked_rx.try_recv(), Ok(Message::Close(None))));
+        assert!(tokio::time::timeout(still_open, kicked_tx.closed()).await.is_ok());
+        assert!(!state.get_room(&room_code).unwrap().players.contains_key(&ids[2]));
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::PlayerKicked { player, .. } if player.id == ids[2])));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_sends_single_full_sync() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (drawer, words) = start_game_with_choices(&state, &room_code, &ids).await;
+        let token = state.issue_auth_token(drawer);
+
+        let (tx, mut rx) = crate::state::connection_channel();
+        let (mut current_player_id, mut current_room_code) = (None, None);
+        handle_reconnect(
+            &state, &room_code, &drawer.to_string(), &token, &tx,
+            &mut current_player_id, &mut current_room_code,
+        ).await;
+
+        let messages = drain(&mut rx);
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            ServerMessage::FullSync { room, word_choices, .. } => {
+                assert_eq!(room.current_drawer, Some(drawer));
+                assert_eq!(word_choices, &words);
+            }
+            other => panic!("expected FullSync, got {:?}", other),
+        }
+        assert_eq!(current_player_id, Some(drawer));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_closes_the_old_socket_and_releases_a_prior_binding() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (old_tx, mut old_rx) = crate::state::connection_channel();
+        state.add_connection(ids[0], room_code.clone(), old_tx.clone());
+        // The new socket starts out bound to b
+        let (tx, _rx) = crate::state::connection_channel();
+        state.add_connection(ids[1], room_code.clone(), tx.clone());
+        let (mut current_player_id, mut current_room_code) = (Some(ids[1]), Some(room_code.clone()));
+        let token = state.issue_auth_token(ids[0]);
+
+        handle_reconnect(
+            &state, &room_code, &ids[0].to_string(), &token, &tx,
+            &mut current_player_id, &mut current_room_code,
+        ).await;
+
+        // The old socket may still see b drop, but its last word is a Fatal error, then the close
+        let mut old_messages = Vec::new();
+        let closing = loop {
+            match old_rx.try_recv() {
+                Ok(Message::Text(text)) => old_messages.push(serde_json::from_str::<ServerMessage>(&text).unwrap()),
+                other => break other,
+            }
+        };
+        assert!(matches!(
+            old_messages.last(),
+            Some(ServerMessage::Error { severity: crate::models::ErrorSeverity::Fatal, .. })
+        ));
+        assert!(matches!(closing, Ok(Message::Close(None))));
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(20), old_tx.closed()).await.is_ok());
+        assert!(state.owns_connection(&ids[0], &tx));
+        assert!(!state.owns_connection(&ids[0], &old_tx));
+        assert!(!state.connections.contains_key(&ids[1]));
+        assert!(!state.get_room(&room_code).unwrap().players[&ids[1]].is_connected);
+    }
+
+    #[tokio::test]
+    async fn test_winner_reconnecting_mid_round_still_sees_word() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "winner", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.winners.push(ids[1]));
+        let _old_rx = connect(&state, &room_code, ids[1]);
+        handle_disconnect(&state, ids[1], Some(&room_code)).await;
+        let token = state.issue_auth_token(ids[1]);
+
+        let (tx, mut rx) = crate::state::connection_channel();
+        let (mut current_player_id, mut current_room_code) = (None, None);
+        handle_reconnect(
+            &state, &room_code, &ids[1].to_string(), &token, &tx,
+            &mut current_player_id, &mut current_room_code,
+        ).await;
+        state.broadcast_room_state_filtered(&room_code);
+
+        let words: Vec<Option<String>> = drained!(&mut rx, ServerMessage::FullSync { room, .. } | ServerMessage::GameStateUpdate { room } => room.word);
+        assert_eq!(words, vec![Some("apple".to_string()), Some("apple".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_drawer_reconnecting_resumes_paused_round() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            pause_for_disconnected_drawer: true,
+            disconnect_grace_secs: 60,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids, mut guesser) = round_with_guesser(&state, "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.round_end_time = Some(chrono::Utc::now() + chrono::Duration::seconds(40));
+        });
+        let _old_rx = connect(&state, &room_code, ids[0]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.current_drawer, Some(ids[0]));
+        assert!(room.round_end_time.is_none());
+        assert!(room.paused_remaining_ms.is_some_and(|ms| ms > 39_000));
+        assert!(drain(&mut guesser).iter().any(|m| matches!(m, ServerMessage::DrawerDisconnected { drawer_id, .. } if *drawer_id == ids[0])));
+
+        let token = state.issue_auth_token(ids[0]);
+        let (tx, mut rx) = crate::state::connection_channel();
+        let (mut current_player_id, mut current_room_code) = (None, None);
+        handle_reconnect(
+            &state, &room_code, &ids[0].to_string(), &token, &tx,
+            &mut current_player_id, &mut current_room_code,
+        ).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(room.paused_remaining_ms.is_none());
+        assert!(room.round_end_time.is_some());
+        assert!(state.round_timers.contains_key(&room_code));
+        assert!(drain(&mut guesser).iter().any(|m| matches!(m, ServerMessage::DrawerReconnected { round_time_remaining_secs: 40, .. })));
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::FullSync { round_time_remaining_secs: Some(39..=40), .. })));
+    }
+
+    #[tokio::test]
+    async fn test_paused_round_rotates_when_drawer_stays_away() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            pause_for_disconnected_drawer: true,
+            disconnect_grace_secs: 0,
+            round_intermission_secs: 0,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["artist", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.round_end_time = Some(chrono::Utc::now() + chrono::Duration::seconds(40));
+        });
+        let _old_rx = connect(&state, &room_code, ids[0]);
+        let mut guesser = connect(&state, &room_code, ids[1]);
+
+        handle_disconnect(&state, ids[0], Some(&room_code)).await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert!(!room.players.contains_key(&ids[0]));
+        assert_ne!(room.current_drawer, Some(ids[0]));
+        assert!(room.paused_remaining_ms.is_none());
+        assert!(drain(&mut guesser).iter().any(|m| matches!(m, ServerMessage::DrawerChanged { reason: DrawerChangeReason::Disconnect, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_scoreboard_request_returns_ordered_standings() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["alice", "bob", "carol"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.players.get_mut(&ids[0]).unwrap().score = 120;
+            room.players.get_mut(&ids[1]).unwrap().score = 300;
+            room.players.get_mut(&ids[2]).unwrap().score = 45;
+        });
+        let mut others = connect(&state, &room_code, ids[0]);
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_request_scoreboard(&state, &room_code, ids[2], &tx).await;
+
+        let Some(ServerMessage::Scoreboard { rankings }) = drain(&mut rx).pop() else {
+            panic!("expected a scoreboard");
+        };
+        let standings: Vec<(&str, u32)> = rankings.iter().map(|r| (r.username.as_str(), r.score)).collect();
+        assert_eq!(standings, vec![("bob", 300), ("alice", 120), ("carol", 45)]);
+        assert!(drain(&mut others).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restarting_round_timer_aborts_the_previous_one() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+
+        start_round_timer(&state, &room_code, 60, round_token);
+        let first = state.round_timers.get(&room_code).unwrap().clone();
+        start_round_timer(&state, &room_code, 60, round_token);
+        let second = state.round_timers.get(&room_code).unwrap().clone();
+
+        for _ in 0..10 {
+            if first.is_finished() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(first.is_finished());
+        assert!(!second.is_finished());
+        assert_eq!(state.round_timers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restart_and_removal_stop_the_game_clock() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+        state.with_room_mut(&room_code, |room| room.max_game_duration_secs = Some(600));
+        let (tx, _rx) = crate::state::connection_channel();
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        let first = state.game_clocks.get(&room_code).unwrap().clone();
+
+        state.with_room_mut(&room_code, |room| room.game_state = crate::models::GameState::Finished);
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        let second = state.game_clocks.get(&room_code).unwrap().clone();
+        state.remove_room(&room_code);
+
+        for _ in 0..10 {
+            if first.is_finished() && second.is_finished() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(first.is_finished() && second.is_finished());
+        assert!(state.game_clocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_removing_room_aborts_its_round_timer() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        start_round_timer(&state, &room_code, 60, round_token);
+        let timer = state.round_timers.get(&room_code).unwrap().clone();
+
+        state.remove_room(&room_code);
+
+        for _ in 0..10 {
+            if timer.is_finished() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(timer.is_finished());
+        assert!(state.round_timers.get(&room_code).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_round_start_carries_round_metadata() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+        state.with_room_mut(&room_code, |room| room.max_rounds = 4);
+        let mut rx = connect(&state, &room_code, ids[1]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let metadata = drained!(&mut rx, ServerMessage::RoundStart { round_number, cycle_number, max_rounds, round_duration, .. } => {
+            (round_number, cycle_number, max_rounds, round_duration)
+        }).into_iter().next();
+        assert_eq!(metadata, Some((room.round_number, room.cycle_number, 4, room.round_duration)));
+    }
+
+    #[tokio::test]
+    async fn test_only_host_starts_and_never_a_live_game() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_start_game(&state, &room_code, ids[1], true, &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().game_state, crate::models::GameState::Waiting);
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        state.with_room_mut(&room_code, |room| room.players.get_mut(&ids[1]).unwrap().score = 150);
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+        drain(&mut rx);
+
+        handle_start_game(&state, &room_code, ids[0], true, &tx).await;
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!((room.round_token, room.players[&ids[1]].score), (round_token, 150));
+        assert!(matches!(drain(&mut rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_start_waits_for_everyone_ready_when_required() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+        state.with_room_mut(&room_code, |room| room.require_ready = true);
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_set_ready(&state, &room_code, ids[0], true, &tx).await;
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().game_state, crate::models::GameState::Waiting);
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+
+        handle_set_ready(&state, &room_code, ids[1], true, &tx).await;
+        handle_start_game(&state, &room_code, ids[0], false, &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().game_state, crate::models::GameState::Playing);
+    }
+
+    /// WS join as the named player, with a valid token when they're registered
+    async fn ws_join(state: &AppState, room_code: &str, username: &str) -> Vec<ServerMessage> {
+        let token = state
+            .get_room(room_code)
+            .and_then(|room| room.players.values().find(|p| p.username == username).map(|p| p.id))
+            .map(|id| state.issue_auth_token(id))
+            .unwrap_or_default();
+        let (tx, mut rx) = crate::state::connection_channel();
+        let (mut player_id, mut joined_room) = (None, None);
+        handle_join_room(state, room_code, username, &token, &tx, &mut player_id, &mut joined_room).await;
+        drain(&mut rx)
+    }
+
+    fn join_error(messages: &[ServerMessage]) -> Option<JoinErrorCode> {
+        messages.iter().find_map(|m| match m {
+            ServerMessage::JoinError { error_code, .. } => Some(*error_code),
+            _ => None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_ws_join_errors_are_distinct() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "guest"]);
+
+        assert_eq!(join_error(&ws_join(&state, "NOPE00", "host").await), Some(JoinErrorCode::RoomNotFound));
+        assert_eq!(join_error(&ws_join(&state, &room_code, "stranger").await), Some(JoinErrorCode::NotRegistered));
+
+        // Knowing the username isn't enough to take over the player
+        let (tx, mut rx) = crate::state::connection_channel();
+        let (mut player_id, mut joined_room) = (None, None);
+        handle_join_room(&state, &room_code, "guest", "guessed", &tx, &mut player_id, &mut joined_room).await;
+        assert_eq!(join_error(&drain(&mut rx)), Some(JoinErrorCode::InvalidToken));
+        assert_eq!(player_id, None);
+        assert!(!state.connections.contains_key(&ids[1]));
+
+        let _guest_rx = connect(&state, &room_code, ids[1]);
+        assert_eq!(join_error(&ws_join(&state, &room_code, "guest").await), Some(JoinErrorCode::AlreadyConnected));
+
+        state.with_room_mut(&room_code, |room| {
+            room.max_players = 2;
+            room.allow_spectators = false;
+        });
+        assert_eq!(join_error(&ws_join(&state, &room_code, "stranger").await), Some(JoinErrorCode::RoomFull));
+
+        let joined = ws_join(&state, &room_code, "host").await;
+        assert_eq!(join_error(&joined), None);
+        assert!(matches!(joined.first(), Some(ServerMessage::PlayerJoined { .. })));
+        assert!(state.connections.contains_key(&ids[0]));
+    }
+
+    #[tokio::test]
+    async fn test_selection_countdown_runs_until_word_selected() {
+        let state = AppState::with_config(crate::config::ServerConfig {
+            word_select_secs: 5,
+            ..crate::config::ServerConfig::default()
+        });
+        let (room_code, ids) = room_with_players(&state, &["a", "b"]);
+        let (drawer, words) = start_game_with_choices(&state, &room_code, &ids).await;
+        let guesser = if drawer == ids[0] { ids[1] } else { ids[0] };
+        let mut rx = connect(&state, &room_code, guesser);
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let ticks = countdown_ticks(&mut rx);
+        assert!(!ticks.is_empty());
+        assert!(ticks.windows(2).all(|pair| pair[0] > pair[1]) && ticks.iter().all(|&secs| secs <= 5));
+
+        let (tx, _own_rx) = crate::state::connection_channel();
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), &words[0], &tx).await;
+        drain(&mut rx);
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        assert!(countdown_ticks(&mut rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_host_transfers_to_chosen_player_only_by_host() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b", "c"]);
+        let mut rx = connect(&state, &room_code, ids[1]);
+        let (tx, mut own_rx) = crate::state::connection_channel();
+
+        handle_transfer_host(&state, &room_code, ids[1], ids[1], &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().host_id, ids[0]);
+        assert!(matches!(drain(&mut own_rx).as_slice(), [ServerMessage::Error { .. }]));
+
+        handle_transfer_host(&state, &room_code, ids[0], ids[2], &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().host_id, ids[2]);
+        assert!(matches!(
+            drain(&mut rx).as_slice(),
+            [ServerMessage::HostChanged { new_host }] if new_host.id == ids[2]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disconnected_player_keeps_score_and_seat_during_grace() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["a", "b", "c"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.draw_order = ids.clone();
+            room.players.get_mut(&ids[1]).unwrap().score = 150;
+        });
+
+        handle_disconnect(&state, ids[1], Some(&room_code)).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        let view = AppState::room_view_for(&room, &ids[2]);
+        let graced = &view.players[&ids[1]];
+        assert!(!graced.is_connected);
+        assert_eq!(graced.score, 150);
+        assert!(crate::scoring::rank_players(view.players.values()).iter().any(|r| r.player_id == ids[1]));
+        assert_eq!(room.potential_guessers(), 1);
+
+        // The rotation passes over them while they're away
+        let outcome = state.end_round(&room_code).unwrap();
+        assert_eq!(outcome.next_drawer.map(|p| p.id), Some(ids[2]));
+    }
+
+    #[tokio::test]
+    async fn test_bought_hint_costs_points_and_reveals_only_to_buyer() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "buyer", "other"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| room.players.get_mut(&ids[1]).unwrap().score = 120);
+        let mut other_rx = connect(&state, &room_code, ids[2]);
+        let (tx, mut buyer_rx) = crate::state::connection_channel();
+
+        handle_buy_hint(&state, &room_code, ids[1], &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.players[&ids[1]].score, 120 - room.hint_cost);
+        let reveals: Vec<(usize, char)> = drained!(&mut buyer_rx, ServerMessage::HintReveal { index, letter, .. } => (index, letter));
+        let [(index, letter)] = reveals.as_slice() else {
+            panic!("expected one hint, got {:?}", reveals);
+        };
+        assert_eq!("apple".chars().nth(*index), Some(*letter));
+        assert!(!drain(&mut other_rx).iter().any(|m| matches!(m, ServerMessage::HintReveal { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_reroll_offers_new_words_once_per_round() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        let original = vec!["castle".to_string(), "rocket".to_string(), "pizza".to_string()];
+        state.with_room_mut(&room_code, |room| {
+            room.word = None;
+            room.pending_word_choices = original.clone();
+        });
+        let mut drawer_rx = connect(&state, &room_code, ids[0]);
+        let (tx, mut tx_rx) = crate::state::connection_channel();
+
+        handle_reroll_words(&state, &room_code, ids[0], &tx).await;
+        let rerolled = state.get_room(&room_code).unwrap().pending_word_choices;
+        assert_eq!(rerolled.len(), crate::words::WORD_CHOICE_COUNT);
+        assert!(rerolled.iter().all(|w| !original.contains(w)));
+        assert!(matches!(drain(&mut drawer_rx).as_slice(), [ServerMessage::WordChoices { words }] if *words == rerolled));
+
+        handle_reroll_words(&state, &room_code, ids[0], &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().pending_word_choices, rerolled);
+        assert!(matches!(drain(&mut tx_rx).as_slice(), [ServerMessage::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_canvas_survives_intermission_when_cleared_on_word_select() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["artist", "guesser"]);
+        start_round(&state, &room_code, ids[0], "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.clear_canvas_on = crate::models::ClearTiming::WordSelect;
+            room.drawing_paths.push(crate::models::DrawPath {
+                id: Uuid::new_v4(),
+                player_id: ids[0],
+                color: crate::models::Color::Black,
+                color_hex: "#000000".to_string(),
+                brush_size: crate::models::BrushSize::Small,
+                strokes: Vec::new(),
+                created_at: chrono::Utc::now(),
+            });
+        });
+
+        state.end_round(&room_code).unwrap();
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.drawing_paths.len(), 1);
+
+        let (tx, _rx) = crate::state::connection_channel();
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), &room.pending_word_choices[0], &tx).await;
+        assert!(state.get_room(&room_code).unwrap().drawing_paths.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_word_pattern_waits_for_show_pattern_after() {
+        let state = AppState::new();
+        let (room_code, _, mut guesser_rx) = round_with_guesser(&state, "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.word = None;
+            room.round_duration = 2;
+            room.show_pattern_after = 0.25;
+            room.pending_word_choices = vec!["ice cream".to_string()];
+        });
+        let (tx, _rx) = crate::state::connection_channel();
+
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), "ice cream", &tx).await;
+        assert!(hint_patterns(&mut guesser_rx).is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(700)).await;
+        assert_eq!(hint_patterns(&mut guesser_rx), vec!["___ _____"]);
+    }
+
+    #[test]
+    fn test_three_hints_spread_over_back_half_of_long_word_round() {
+        assert_eq!(crate::words::hint_reveal_count("hippopotamus", 3, 4), 3);
+        assert_eq!(hint_reveal_times_ms(80, 3), vec![50_000, 60_000, 70_000]);
+        // Never more reveals than would show the whole word
+        assert_eq!(crate::words::hint_reveal_count("ox", 3, 4), 1);
+    }
+
+    #[tokio::test]
+    async fn test_hint_schedule_reveals_three_letters_in_back_half() {
+        let state = AppState::new();
+        let (room_code, _, mut guesser_rx) = round_with_guesser(&state, "hippopotamus");
+        let round_token = state.get_room(&room_code).unwrap().round_token;
+
+        // A 1s round reveals at 625ms, 750ms and 875ms
+        let mut order = crate::words::letter_positions("hippopotamus");
+        order.truncate(3);
+        start_hint_schedule(&state, &room_code, "hippopotamus", round_token, 1, order, 0);
+        tokio::time::sleep(std::time::Duration::from_millis(450)).await;
+        assert!(hint_patterns(&mut guesser_rx).is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(550)).await;
+        let shown: Vec<usize> = hint_patterns(&mut guesser_rx).iter().map(|p| p.chars().filter(|c| *c != '_').count()).collect();
+        assert_eq!(shown, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_start_reveal_shows_a_letter_in_the_first_pattern() {
+        let state = AppState::new();
+        let (room_code, _, mut guesser_rx) = round_with_guesser(&state, "apple");
+        state.with_room_mut(&room_code, |room| {
+            room.word = None;
+            room.start_reveal = 1;
+            room.pending_word_choices = vec!["elephant".to_string()];
+        });
+        let (tx, _rx) = crate::state::connection_channel();
+
+        handle_word_selected(&state, &room_code, drawer_of(&state, &room_code), "elephant", &tx).await;
+
+        let first = hint_patterns(&mut guesser_rx).into_iter().next().expect("pattern sent at round start");
+        assert_eq!(first.chars().filter(|c| *c != '_').count(), 1);
+        assert!(first.chars().zip("elephant".chars()).all(|(shown, letter)| shown == '_' || shown == letter));
+    }
+
+    #[test]
+    fn test_start_reveal_counts_toward_max_hint_reveal() {
+        assert_eq!(HintReveals::for_word("elephant", 1, 2, 4), HintReveals { at_start: 1, scheduled: 2 });
+        assert_eq!(HintReveals::for_word("elephant", 2, 3, 4), HintReveals { at_start: 2, scheduled: 2 });
+        assert_eq!(HintReveals::for_word("cat", 1, 2, 4), HintReveals { at_start: 1, scheduled: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_majority_play_again_vote_restarts_the_game() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b", "c"]);
+        state.with_room_mut(&room_code, |room| {
+            room.game_state = crate::models::GameState::Finished;
+            for player in room.players.values_mut() {
+                player.score = 300;
+            }
+        });
+        let mut rx = connect(&state, &room_code, ids[0]);
+        let (tx, _own_rx) = crate::state::connection_channel();
+
+        handle_play_again(&state, &room_code, ids[1], &tx).await;
+        assert_eq!(state.get_room(&room_code).unwrap().game_state, crate::models::GameState::Finished);
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::PlayAgainUpdate { needed: 2, .. })));
+
+        handle_play_again(&state, &room_code, ids[2], &tx).await;
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.game_state, crate::models::GameState::Playing);
+        assert!(room.players.values().all(|p| p.score == 0));
+        assert!(room.play_again_votes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refused_play_again_keeps_final_scores() {
+        let state = AppState::new();
+        let (room_code, ids) = room_with_players(&state, &["host", "b"]);
+        state.with_room_mut(&room_code, |room| {
+            room.game_state = crate::models::GameState::Finished;
+            room.players.get_mut(&ids[0]).unwrap().score = 300;
+            room.players.get_mut(&ids[1]).unwrap().is_connected = false;
+        });
+        let (tx, mut rx) = crate::state::connection_channel();
+
+        handle_play_again(&state, &room_code, ids[0], &tx).await;
+
+        let room = state.get_room(&room_code).unwrap();
+        assert_eq!(room.game_state, crate::models::GameState::Finished);
+        assert_eq!(room.players[&ids[0]].score, 300);
+        assert!(drain(&mut rx).iter().any(|m| matches!(m, ServerMessage::Error { .. })));
+    }
+}
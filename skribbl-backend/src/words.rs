@@ -0,0 +1,237 @@
+use crate::models::{GuessMatching, Room, WordRejection};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+// Number of words offered to the drawer at the start of each round
+pub const WORD_CHOICE_COUNT: usize = 3;
+
+// Default shortest word a room will offer; shorter words are trivial to guess
+pub const DEFAULT_MIN_WORD_LENGTH: u32 = 3;
+
+// Bundled word bank used when a room has no custom list
+pub const WORD_BANK: &[&str] = &[
+    "apple", "banana", "guitar", "elephant", "castle", "rocket", "pizza", "umbrella",
+    "volcano", "penguin", "bicycle", "lighthouse", "dragon", "snowman", "airplane", "island",
+    "camera", "pirate", "rainbow", "spider", "treasure", "waterfall", "wizard", "zebra",
+    "anchor", "balloon", "cactus", "dolphin", "envelope", "feather", "giraffe", "hammer",
+    "igloo", "jellyfish", "kangaroo", "ladder", "mermaid", "necklace", "octopus", "parachute",
+    "queen", "robot", "sandwich", "telescope", "unicorn", "vampire", "windmill", "yacht",
+    "bridge", "candle", "diamond", "fireworks", "ghost", "hamburger", "iceberg", "jungle",
+    "kite", "lemon", "mountain", "nest", "owl", "pencil", "river", "scissors",
+    "tornado", "violin", "whale", "backpack", "clock", "dinosaur", "eagle", "flower",
+    "helicopter", "key", "lion", "moon", "ninja", "pumpkin", "rabbit", "skateboard",
+    "tiger", "turtle", "crown", "cookie", "football", "glasses", "hospital", "keyboard",
+    "magnet", "mushroom", "painting", "popcorn", "sun", "sword", "tree", "train",
+];
+
+/// Sample `count` distinct words of at least `min_len` characters from the room's
+/// custom list, or from the bank when the room has none, never offering any word in `excluded`
+pub fn pick_word_choices_excluding(custom_words: &[String], min_len: u32, count: usize, excluded: &[String]) -> Vec<String> {
+    pick_word_choices_with(custom_words, min_len, count, excluded, &mut rand::thread_rng())
+}
+
+/// Offer the room's next set of choices. Verifiable rooms draw from their committed seed
+/// (draw N uses `seeded_draw_rng(seed, N)`), so the sequence can be replayed once it's revealed.
+pub fn pick_word_choices_for(room: &mut Room, excluded: &[String]) -> Vec<String> {
+    let Some(seed) = room.word_seed else {
+        return pick_word_choices_excluding(&room.custom_words, room.min_word_length, WORD_CHOICE_COUNT, excluded);
+    };
+    let mut rng = seeded_draw_rng(&seed, room.word_draws);
+    room.word_draws += 1;
+    pick_word_choices_with(&room.custom_words, room.min_word_length, WORD_CHOICE_COUNT, excluded, &mut rng)
+}
+
+/// Sample choices with the given generator; the pool is in a fixed order so a seeded
+/// generator always yields the same words
+pub fn pick_word_choices_with<R: Rng + ?Sized>(
+    custom_words: &[String],
+    min_len: u32,
+    count: usize,
+    excluded: &[String],
+    rng: &mut R,
+) -> Vec<String> {
+    let usable = |w: &&str| w.chars().count() >= min_len as usize && !excluded.iter().any(|e| is_word_match(w, e, GuessMatching::Strict));
+    let pool: Vec<&str> = if custom_words.is_empty() {
+        WORD_BANK.iter().copied().filter(usable).collect()
+    } else {
+        custom_words.iter().map(String::as_str).filter(usable).collect()
+    };
+    pool.choose_multiple(rng, count)
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// A fresh secret for a verifiable game; only its hash is published until the game ends
+pub fn new_word_seed() -> [u8; 32] {
+    rand::random()
+}
+
+/// The published commitment to a seed: its SHA-256, hex-encoded
+pub fn seed_commitment(seed: &[u8; 32]) -> String {
+    to_hex(&Sha256::digest(seed))
+}
+
+/// Generator for a verifiable room's Nth word draw: ChaCha20 keyed by SHA-256(seed || N as u64 LE)
+pub fn seeded_draw_rng(seed: &[u8; 32], draw: u64) -> ChaCha20Rng {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update(draw.to_le_bytes());
+    ChaCha20Rng::from_seed(hasher.finalize().into())
+}
+
+/// Lowercase hex encoding
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Words refused in custom lists, matched case-insensitively against each whole word of an entry
+const BLOCKED_WORDS: &[&str] = &["fuck", "shit", "bitch", "cunt", "dick", "asshole", "bastard", "whore"];
+
+/// Check each entry of a custom word list: trim, then drop words shorter than `min_len`,
+/// blocked words and duplicates. Returns the usable words in order and the rejections.
+pub fn review_custom_words(words: &[String], min_len: u32) -> (Vec<String>, Vec<WordRejection>) {
+    let mut cleaned: Vec<String> = Vec::new();
+    let mut rejected: Vec<WordRejection> = Vec::new();
+    for word in words.iter().map(|w| w.trim()) {
+        let reason = if word.chars().count() < min_len as usize {
+            Some(format!("shorter than {} characters", min_len))
+        } else if word
+            .split_whitespace()
+            .any(|part| BLOCKED_WORDS.iter().any(|blocked| part.eq_ignore_ascii_case(blocked)))
+        {
+            Some("not allowed".to_string())
+        } else if cleaned.iter().any(|w| w.eq_ignore_ascii_case(word)) {
+            Some("duplicate".to_string())
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => rejected.push(WordRejection { word: word.to_string(), reason }),
+            None => cleaned.push(word.to_string()),
+        }
+    }
+    (cleaned, rejected)
+}
+
+/// Clean up a custom word list as `review_custom_words` does.
+/// Fails if too few words remain to offer a full set of choices.
+pub fn validate_custom_words(words: &[String], min_len: u32) -> Result<Vec<String>, String> {
+    let (cleaned, _) = review_custom_words(words, min_len);
+    if cleaned.len() < WORD_CHOICE_COUNT {
+        return Err(format!(
+            "Custom word list needs at least {} words of {} or more characters",
+            WORD_CHOICE_COUNT, min_len
+        ));
+    }
+    Ok(cleaned)
+}
+
+/// Whether typed text names `word`; matching ignores case and surrounding whitespace.
+/// The stored word (bank or custom, kept as entered) is always the display form.
+pub fn is_word_match(typed: &str, word: &str, matching: GuessMatching) -> bool {
+    normalize_guess(typed, matching) == normalize_guess(word, matching)
+}
+
+/// Edit distance between a guess and the word, compared the same way as is_word_match
+pub fn guess_distance(typed: &str, word: &str, matching: GuessMatching) -> usize {
+    let typed: Vec<char> = normalize_guess(typed, matching).chars().collect();
+    let word: Vec<char> = normalize_guess(word, matching).chars().collect();
+    let mut previous: Vec<usize> = (0..=word.len()).collect();
+    for (i, &t) in typed.iter().enumerate() {
+        let mut current = vec![i + 1; word.len() + 1];
+        for (j, &w) in word.iter().enumerate() {
+            let substitution = previous[j] + usize::from(t != w);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[word.len()]
+}
+
+/// Fold text for guess comparison: trimmed and lowercased, and under Lenient matching
+/// with look-alike letters replaced by the ASCII letter they're mistaken for
+pub fn normalize_guess(text: &str, matching: GuessMatching) -> String {
+    let lowered = text.trim().to_lowercase();
+    match matching {
+        GuessMatching::Strict => lowered,
+        GuessMatching::Lenient => lowered.chars().map(ascii_lookalike).collect(),
+    }
+}
+
+/// The ASCII letter or digit a lowercase confusable renders like, or the char unchanged
+fn ascii_lookalike(c: char) -> char {
+    match c {
+        // Cyrillic
+        'а' => 'a', 'в' => 'b', 'е' | 'ё' => 'e', 'һ' => 'h', 'і' | 'ї' => 'i', 'ј' => 'j',
+        'к' => 'k', 'м' => 'm', 'н' => 'h', 'о' => 'o', 'р' => 'p', 'с' => 'c', 'ѕ' => 's',
+        'т' => 't', 'у' => 'y', 'х' => 'x', 'ԁ' => 'd', 'ԛ' => 'q', 'ԝ' => 'w',
+        // Greek
+        'α' => 'a', 'ε' => 'e', 'ι' => 'i', 'κ' => 'k', 'ν' => 'v', 'ο' => 'o', 'ρ' => 'p',
+        'τ' => 't', 'υ' => 'u', 'χ' => 'x',
+        // Full-width forms
+        'ａ'..='ｚ' => char::from(b'a' + (c as u32 - 'ａ' as u32) as u8),
+        '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+        _ => c,
+    }
+}
+
+/// The word as guessers may see it: each letter or digit blanked to "_" unless its char
+/// position is in `revealed`, anything else kept
+pub fn word_pattern_revealing(word: &str, revealed: &[usize]) -> String {
+    word.chars()
+        .enumerate()
+        .map(|(i, c)| if c.is_alphanumeric() && !revealed.contains(&i) { '_' } else { c })
+        .collect()
+}
+
+/// Char positions of the word's letters and digits, the ones a pattern blanks out
+pub fn letter_positions(word: &str) -> Vec<usize> {
+    word.chars().enumerate().filter(|(_, c)| c.is_alphanumeric()).map(|(i, _)| i).collect()
+}
+
+/// How many letters a round's hints reveal: the room's hint_count, held under the server
+/// ceiling and always leaving at least one letter hidden
+pub fn hint_reveal_count(word: &str, hint_count: u32, max_hint_reveal: u32) -> usize {
+    let letters = word.chars().filter(|c| c.is_alphanumeric()).count();
+    (hint_count.min(max_hint_reveal) as usize).min(letters.saturating_sub(1))
+}
+
+/// Find the offered choice matching `word` (case-insensitive), returning the stored form
+pub fn match_word_choice<'a>(choices: &'a [String], word: &str) -> Option<&'a String> {
+    choices.iter().find(|choice| is_word_match(word, choice, GuessMatching::Strict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cyrillic_lookalike_guess_matches_only_when_lenient() {
+        // "аpple" with a Cyrillic 'а', "ΑРРLЕ" mixing Greek and Cyrillic capitals
+        assert!(is_word_match("\u{0430}pple", "apple", GuessMatching::Lenient));
+        assert!(is_word_match("\u{0391}\u{0420}\u{0420}L\u{0415}", "Apple", GuessMatching::Lenient));
+        assert!(is_word_match("ａｐｐｌｅ", "apple", GuessMatching::Lenient));
+        assert!(!is_word_match("\u{0430}pple", "apple", GuessMatching::Strict));
+        assert!(!is_word_match("\u{0430}pples", "apple", GuessMatching::Lenient));
+    }
+
+    #[test]
+    fn test_short_words_are_never_offered() {
+        let custom: Vec<String> = ["ox", "cat", "dog", "cow"].iter().map(|w| w.to_string()).collect();
+        for _ in 0..20 {
+            let mut choices = pick_word_choices_excluding(&custom, 3, WORD_CHOICE_COUNT, &[]);
+            choices.sort();
+            assert_eq!(choices, vec!["cat", "cow", "dog"]);
+        }
+        assert!(WORD_BANK.iter().any(|w| w.len() < 4));
+        assert!(pick_word_choices_excluding(&[], 4, WORD_BANK.len(), &[]).iter().all(|w| w.len() >= 4));
+    }
+
+    #[test]
+    fn test_custom_list_of_short_words_is_rejected() {
+        let custom: Vec<String> = ["ox", "ax", "go", "cat"].iter().map(|w| w.to_string()).collect();
+        assert!(validate_custom_words(&custom, 3).is_err());
+    }
+}